@@ -31,7 +31,7 @@ use std::io::Read;
 use std::net::SocketAddr;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpStream};
 use tokio::runtime::Runtime;
 use tracing::info;
 
@@ -59,13 +59,20 @@ impl MockServer {
         })
         .write_fields()
         .expect("Encoding event");
-        let (mut stream, _addr) = self.listener.accept().await.expect("get incoming stream");
+        // the client opens a new connection when the pooled ones are in use, serve each of them.
+        loop {
+            let (stream, _addr) = self.listener.accept().await.expect("get incoming stream");
+            tokio::spawn(MockServer::serve(stream, event_data.clone()));
+        }
+    }
+
+    async fn serve(mut stream: TcpStream, event_data: Vec<u8>) {
         loop {
             let mut header: Vec<u8> = vec![0; LENGTH_FIELD_OFFSET as usize + LENGTH_FIELD_LENGTH as usize];
-            stream
-                .read_exact(&mut header[..])
-                .await
-                .expect("read header from incoming stream");
+            if stream.read_exact(&mut header[..]).await.is_err() {
+                // the connection is closed by the client.
+                return;
+            }
             let mut rdr = Cursor::new(&header[4..8]);
             let payload_length =
                 byteorder::ReadBytesExt::read_u32::<BigEndian>(&mut rdr).expect("exact size");
@@ -189,7 +196,7 @@ impl MockServer {
 
 // Read a segment slice and consume events from the slice.
 async fn run_reader(reader: &mut EventReader, last_offset: &mut i64) {
-    if let Some(mut slice) = reader.acquire_segment().await {
+    if let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
         while let Some(e) = slice.next() {
             // validate offset in the segment.
             if *last_offset == -1i64 {
@@ -212,6 +219,31 @@ async fn run_reader(reader: &mut EventReader, last_offset: &mut i64) {
     }
 }
 
+// Read a segment slice and consume events from the slice without copying their data.
+async fn run_reader_bytes(reader: &mut EventReader, last_offset: &mut i64) {
+    if let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
+        while let Some(e) = slice.next_bytes() {
+            // validate offset in the segment.
+            if *last_offset == -1i64 {
+                assert_eq!(0, e.offset_in_segment);
+            } else {
+                assert_eq!(
+                    READ_EVENT_SIZE_BYTES + 2 * TYPE_PLUS_LENGTH_SIZE as usize,
+                    (e.offset_in_segment - *last_offset) as usize
+                );
+            }
+            // validate the event read length
+            assert_eq!(
+                READ_EVENT_SIZE_BYTES + TYPE_PLUS_LENGTH_SIZE as usize,
+                e.value.len()
+            );
+            *last_offset = e.offset_in_segment;
+        }
+    } else {
+        assert!(false, "No slice acquired");
+    }
+}
+
 // This benchmark test uses a mock server that replies ok to any requests instantly. It involves
 // kernel latency.
 fn event_stream_read_mock_server(c: &mut Criterion) {
@@ -234,6 +266,28 @@ fn event_stream_read_mock_server(c: &mut Criterion) {
     });
     println!("reader performance testing finished");
 }
+
+// Same as event_stream_read_mock_server except that the events are read without copying their data.
+fn event_stream_read_bytes_mock_server(c: &mut Criterion) {
+    let mut rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(MockServer::new());
+    let config = ClientConfigBuilder::default()
+        .controller_uri(mock_server.address)
+        .mock(true)
+        .build()
+        .expect("creating config");
+    rt.spawn(async { MockServer::run(mock_server).await });
+    let mut reader = rt.block_on(set_up_event_stream_reader(config));
+    let _ = tracing_subscriber::fmt::try_init();
+    info!("start bytes reader with mock server performance testing");
+    let mut last_offset: i64 = -1;
+    c.bench_function("read 100KB mock server without copy", |b| {
+        b.iter(|| {
+            rt.block_on(run_reader_bytes(&mut reader, &mut last_offset));
+        });
+    });
+    println!("bytes reader performance testing finished");
+}
 // This benchmark test uses a mock server that replies ok to any requests instantly. It involves
 // kernel latency.
 fn event_stream_writer_mock_server(c: &mut Criterion) {
@@ -366,9 +420,13 @@ async fn set_up_event_stream_reader(config: ClientConfig) -> EventReader {
     };
     let reader_group = client_factory
         .create_reader_group(scope_name, "rg1".to_string(), scoped_stream)
-        .await;
+        .await
+        .expect("create reader group");
 
-    let reader = reader_group.create_reader("r1".to_string()).await;
+    let reader = reader_group
+        .create_reader("r1".to_string())
+        .await
+        .expect("create reader");
     reader
 }
 
@@ -465,7 +523,7 @@ criterion_group! {
 criterion_group! {
     name = event_reader_performance;
     config = Criterion::default().sample_size(10);
-    targets = event_stream_read_mock_server
+    targets = event_stream_read_mock_server,event_stream_read_bytes_mock_server
 }
 criterion_group! {
     name = byte_reader_performance;
@@ -271,13 +271,15 @@ impl StreamManager {
             stream: Stream::from(stream_name.to_string()),
         };
         let handle = self.cf.get_runtime();
-        let rg = handle.block_on(self.cf.create_reader_group(
+        let rg_result = handle.block_on(self.cf.create_reader_group(
             scope,
             reader_group_name.to_string(),
             scoped_stream.clone(),
         ));
-        let reader_group = StreamReaderGroup::new(rg, self.cf.clone(), scoped_stream);
-        Ok(reader_group)
+        match rg_result {
+            Ok(rg) => Ok(StreamReaderGroup::new(rg, self.cf.clone(), scoped_stream)),
+            Err(e) => Err(exceptions::ValueError::py_err(format!("{:?}", e))),
+        }
     }
 
     /// Returns the string representation.
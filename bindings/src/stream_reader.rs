@@ -13,10 +13,12 @@ cfg_if! {
         use pravega_client::event_reader::EventReader;
         use pravega_client_shared::ScopedStream;
         use pravega_client::client_factory::ClientFactory;
+        use pravega_client::reader_group::reader_group_state::ReaderGroupStateError;
+        use pyo3::exceptions;
         use pyo3::prelude::*;
         use pyo3::PyResult;
         use pyo3::PyObjectProtocol;
-        use tracing::info;
+        use tracing::{error, info};
         use std::sync::Arc;
         use pravega_client::segment_slice::{Event, SegmentSlice};
         use pyo3::PyIterProtocol;
@@ -69,7 +71,14 @@ impl StreamReader {
         let read = self.reader.clone();
         let _guard = self.factory.get_runtime().enter();
         tokio::spawn(async move {
-            let slice_result = read.lock().await.acquire_segment().await;
+            // an error is returned as an empty slice, the reader can be polled again.
+            let slice_result = match read.lock().await.acquire_segment().await {
+                Ok(slice) => slice,
+                Err(e) => {
+                    error!("Error while acquiring segment {:?}", e);
+                    None
+                }
+            };
             let slice_py: Slice = Slice {
                 seg_slice: slice_result,
             };
@@ -103,7 +112,12 @@ impl StreamReader {
     pub fn release_segment(&self, slice: &mut Slice) -> PyResult<()> {
         info!("Release segment slice back");
         if let Some(s) = slice.get_set_to_none() {
-            self.factory.get_runtime().block_on(self.release_segment_async(s));
+            if let Err(e) = self.factory.get_runtime().block_on(self.release_segment_async(s)) {
+                return Err(exceptions::ValueError::py_err(format!(
+                    "Error observed while releasing the segment slice {:?}",
+                    e
+                )));
+            }
         }
         Ok(())
     }
@@ -149,8 +163,8 @@ impl StreamReader {
     }
 
     // Helper method for to release segment
-    async fn release_segment_async(&self, slice: SegmentSlice) {
-        self.reader.lock().await.release_segment(slice).await;
+    async fn release_segment_async(&self, slice: SegmentSlice) -> Result<(), ReaderGroupStateError> {
+        self.reader.lock().await.release_segment(slice).await
     }
 }
 
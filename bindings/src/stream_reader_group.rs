@@ -16,7 +16,7 @@ cfg_if! {
         use pravega_client::client_factory::ClientFactory;
         use pyo3::prelude::*;
         use pyo3::PyResult;
-        use pyo3::PyObjectProtocol;
+        use pyo3::{exceptions, PyObjectProtocol};
         use tracing::info;
         use std::sync::Arc;
         use tokio::sync::Mutex;
@@ -63,16 +63,18 @@ impl StreamReaderGroup {
             reader_name,
             self.reader_group.get_name()
         );
-        let reader = self
+        let reader_result = self
             .factory
             .get_runtime()
             .block_on(self.reader_group.create_reader(reader_name.to_string()));
-        let stream_reader = StreamReader::new(
-            Arc::new(Mutex::new(reader)),
-            self.factory.clone(),
-            self.stream.clone(),
-        );
-        Ok(stream_reader)
+        match reader_result {
+            Ok(reader) => Ok(StreamReader::new(
+                Arc::new(Mutex::new(reader)),
+                self.factory.clone(),
+                self.stream.clone(),
+            )),
+            Err(e) => Err(exceptions::ValueError::py_err(format!("{:?}", e))),
+        }
     }
 
     /// Returns the string representation.
@@ -38,8 +38,8 @@ use controller::{
     CreateStreamStatus, CreateTxnRequest, CreateTxnResponse, DelegationToken, DeleteScopeStatus,
     DeleteStreamStatus, GetEpochSegmentsRequest, GetSegmentsRequest, NodeUri, PingTxnRequest, PingTxnStatus,
     ScaleRequest, ScaleResponse, ScaleStatusRequest, ScaleStatusResponse, ScopeInfo, SegmentId,
-    SegmentRanges, SegmentsAtTime, StreamConfig, StreamInfo, SuccessorResponse, TxnId, TxnRequest, TxnState,
-    TxnStatus, UpdateStreamStatus,
+    SegmentRanges, SegmentsAtTime, StreamConfig, StreamCutRange, StreamCutRangeResponse, StreamInfo,
+    SuccessorResponse, TxnId, TxnRequest, TxnState, TxnStatus, UpdateStreamStatus,
 };
 use im::{HashMap as ImHashMap, OrdMap};
 use ordered_float::OrderedFloat;
@@ -181,6 +181,24 @@ pub trait ControllerClient: Send + Sync {
      */
     async fn get_head_segments(&self, stream: &ScopedStream) -> ResultRetry<ImHashMap<Segment, i64>>;
 
+    /**
+     * API to get the segments and offsets of the stream at the given timestamp, which is the
+     * wall clock time in milliseconds since the unix epoch. Segments truncated after the timestamp
+     * are returned at their current head offsets.
+     */
+    async fn get_segments_at_time(
+        &self,
+        stream: &ScopedStream,
+        timestamp: i64,
+    ) -> ResultRetry<ImHashMap<Segment, i64>>;
+
+    /**
+     * API to get all the segments of a stream between the two stream cuts, including the
+     * segments of both stream cuts.
+     */
+    async fn get_segments_between(&self, from: &StreamCut, to: &StreamCut)
+        -> ResultRetry<Vec<ScopedSegment>>;
+
     /**
      * API to create a new transaction. The transaction timeout is relative to the creation time.
      */
@@ -391,6 +409,28 @@ impl ControllerClient for ControllerClientImpl {
         )
     }
 
+    async fn get_segments_at_time(
+        &self,
+        stream: &ScopedStream,
+        timestamp: i64,
+    ) -> ResultRetry<ImHashMap<Segment, i64>> {
+        wrap_with_async_retry!(
+            self.config.retry_policy.max_tries(MAX_RETRIES),
+            self.call_get_segments_at_time(stream, timestamp, "getSegmentsAtTime")
+        )
+    }
+
+    async fn get_segments_between(
+        &self,
+        from: &StreamCut,
+        to: &StreamCut,
+    ) -> ResultRetry<Vec<ScopedSegment>> {
+        wrap_with_async_retry!(
+            self.config.retry_policy.max_tries(MAX_RETRIES),
+            self.call_get_segments_between(from, to)
+        )
+    }
+
     async fn create_transaction(&self, stream: &ScopedStream, lease: Duration) -> ResultRetry<TxnSegments> {
         wrap_with_async_retry!(
             self.config.retry_policy.max_tries(MAX_RETRIES),
@@ -806,18 +846,26 @@ impl ControllerClientImpl {
     }
 
     async fn call_get_head_segments(&self, stream: &ScopedStream) -> Result<ImHashMap<Segment, i64>> {
+        self.call_get_segments_at_time(stream, 0, "getHeadSegments").await
+    }
+
+    async fn call_get_segments_at_time(
+        &self,
+        stream: &ScopedStream,
+        timestamp: i64,
+        operation_name: &str,
+    ) -> Result<ImHashMap<Segment, i64>> {
         let request: StreamInfo = StreamInfo::from(stream);
         let op_status: StdResult<tonic::Response<SegmentsAtTime>, tonic::Status> = self
             .get_controller_client()
             .get_segments(tonic::Request::new(GetSegmentsRequest {
                 stream_info: Some(request),
-                timestamp: 0,
+                timestamp,
             }))
             .await;
-        let operation_name = "getHeadSegments";
         match op_status {
             Ok(segment_ranges) => {
-                let head_segments = segment_ranges
+                let segments = segment_ranges
                     .into_inner()
                     .segments
                     .iter()
@@ -828,12 +876,38 @@ impl ControllerClientImpl {
                         (segment, start_offset)
                     })
                     .collect();
-                Ok(head_segments)
+                Ok(segments)
             }
             Err(status) => Err(self.map_grpc_error(operation_name, status).await),
         }
     }
 
+    async fn call_get_segments_between(
+        &self,
+        from: &StreamCut,
+        to: &StreamCut,
+    ) -> Result<Vec<ScopedSegment>> {
+        let request = StreamCutRange {
+            stream_info: Some(StreamInfo::from(&from.scoped_stream)),
+            from: from.segment_offset_map.clone(),
+            to: to.segment_offset_map.clone(),
+        };
+        let op_status: StdResult<tonic::Response<StreamCutRangeResponse>, tonic::Status> = self
+            .get_controller_client()
+            .get_segments_between(tonic::Request::new(request))
+            .await;
+        let operation_name = "getSegmentsBetween";
+        match op_status {
+            Ok(response) => Ok(response
+                .into_inner()
+                .segments
+                .into_iter()
+                .map(ScopedSegment::from)
+                .collect()),
+            Err(status) => Err(self.map_grpc_error(operation_name, status).await),
+        }
+    }
+
     async fn call_get_epoch_segments(&self, stream: &ScopedStream, epoch: i32) -> Result<StreamSegments> {
         let request: StreamInfo = StreamInfo::from(stream);
         let op_status: StdResult<tonic::Response<SegmentRanges>, tonic::Status> = self
@@ -1321,6 +1395,21 @@ mod test {
             .expect("get head segments");
         assert!(res.is_empty());
 
+        // test get segments at time
+        let res = rt
+            .block_on(controller.get_segments_at_time(&scoped_stream, 1000))
+            .expect("get segments at time");
+        assert!(res.is_empty());
+
+        // test get segments between
+        let res = rt
+            .block_on(controller.get_segments_between(
+                &StreamCut::new(scoped_stream.clone(), HashMap::new()),
+                &StreamCut::new(scoped_stream.clone(), HashMap::new()),
+            ))
+            .expect("get segments between");
+        assert!(res.is_empty());
+
         // test get successors
         let res = rt
             .block_on(controller.get_successors(&scoped_segment))
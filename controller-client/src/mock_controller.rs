@@ -271,6 +271,24 @@ impl ControllerClient for MockController {
             .collect())
     }
 
+    async fn get_segments_at_time(
+        &self,
+        stream: &ScopedStream,
+        _timestamp: i64,
+    ) -> ResultRetry<ImHashMap<Segment, i64>> {
+        // streams of the mock controller never scale nor get truncated.
+        self.get_head_segments(stream).await
+    }
+
+    async fn get_segments_between(
+        &self,
+        from: &StreamCut,
+        _to: &StreamCut,
+    ) -> ResultRetry<Vec<ScopedSegment>> {
+        // streams of the mock controller never scale, so all the segments are between any stream cuts.
+        get_segments_for_stream(&from.scoped_stream, &self.created_streams.read().await)
+    }
+
     async fn create_transaction(
         &self,
         stream: &ScopedStream,
@@ -67,11 +67,16 @@ async fn main() {
     // create event stream reader
     let rg = client_factory
         .create_reader_group(scope, "rg".to_string(), stream)
-        .await;
-    let mut reader = rg.create_reader("r1".to_string()).await;
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
 
     // read from segment
-    let mut slice = reader.acquire_segment().await.expect("acquire segment");
+    let mut slice = reader
+        .acquire_segment()
+        .await
+        .expect("acquire segment")
+        .expect("segment slice");
     let read_event = slice.next();
     assert!(read_event.is_some(), "event slice should have event to read");
     assert_eq!(b"hello world", read_event.unwrap().value.as_slice());
@@ -44,6 +44,7 @@ pub fn test_event_stream_reader(config: PravegaStandaloneServiceConfig) {
     test_multiple_readers(&client_factory);
     test_reader_offline(&client_factory);
     test_segment_rebalance(&client_factory);
+    runtime.block_on(test_reader_group_delete(&client_factory));
 }
 
 fn test_read_large_events(client_factory: &ClientFactory, rt: &Runtime) {
@@ -75,13 +76,16 @@ fn test_read_large_events(client_factory: &ClientFactory, rt: &Runtime) {
         stream: stream_name,
     };
 
-    let rg: ReaderGroup =
-        rt.block_on(client_factory.create_reader_group(scope_name, "rg-large-event".to_string(), stream));
-    let mut reader = rt.block_on(rg.create_reader("r1".to_string()));
+    let rg: ReaderGroup = rt
+        .block_on(client_factory.create_reader_group(scope_name, "rg-large-event".to_string(), stream))
+        .expect("create reader group");
+    let mut reader = rt
+        .block_on(rg.create_reader("r1".to_string()))
+        .expect("create reader");
 
     let mut event_count = 0;
     while event_count < NUM_EVENTS {
-        if let Some(mut slice) = rt.block_on(reader.acquire_segment()) {
+        if let Some(mut slice) = rt.block_on(reader.acquire_segment()).expect("acquire segment") {
             while let Some(event) = slice.next() {
                 assert_eq!(
                     vec![1; EVENT_SIZE],
@@ -91,7 +95,8 @@ fn test_read_large_events(client_factory: &ClientFactory, rt: &Runtime) {
                 event_count += 1;
                 info!("read count {}", event_count);
             }
-            rt.block_on(reader.release_segment(slice));
+            rt.block_on(reader.release_segment(slice))
+                .expect("release segment");
         }
     }
     assert_eq!(event_count, NUM_EVENTS);
@@ -132,19 +137,25 @@ fn test_multi_reader_multi_segments_tail_read(client_factory: &ClientFactory, rt
         stream: stream_name,
     };
 
-    let rg: ReaderGroup = rt.block_on(client_factory.create_reader_group(
-        scope_name,
-        "rg-single-reader-multi-segments".to_string(),
-        stream,
-    ));
-    let mut reader1 = rt.block_on(rg.create_reader("r1".to_string()));
-    let mut reader2 = rt.block_on(rg.create_reader("r2".to_string()));
+    let rg: ReaderGroup = rt
+        .block_on(client_factory.create_reader_group(
+            scope_name,
+            "rg-single-reader-multi-segments".to_string(),
+            stream,
+        ))
+        .expect("create reader group");
+    let mut reader1 = rt
+        .block_on(rg.create_reader("r1".to_string()))
+        .expect("create reader");
+    let mut reader2 = rt
+        .block_on(rg.create_reader("r2".to_string()))
+        .expect("create reader");
     let read_count = Arc::new(AtomicUsize::new(0));
     let read_count1 = read_count.clone();
     let read_count2 = read_count.clone();
     let handle1 = rt.spawn(async move {
         while read_count1.load(Ordering::Relaxed) < NUM_EVENTS {
-            if let Some(mut slice) = reader1.acquire_segment().await {
+            if let Some(mut slice) = reader1.acquire_segment().await.expect("acquire segment") {
                 info!("acquire segment for reader r1, {:?}", slice);
                 while let Some(event) = slice.next() {
                     assert_eq!(
@@ -155,13 +166,13 @@ fn test_multi_reader_multi_segments_tail_read(client_factory: &ClientFactory, rt
                     let prev = read_count1.fetch_add(1, Ordering::SeqCst);
                     info!("read count {}", prev + 1);
                 }
-                reader1.release_segment(slice).await;
+                reader1.release_segment(slice).await.expect("release segment");
             }
         }
     });
     let handle2 = rt.spawn(async move {
         while read_count2.load(Ordering::Relaxed) < NUM_EVENTS {
-            if let Some(mut slice) = reader2.acquire_segment().await {
+            if let Some(mut slice) = reader2.acquire_segment().await.expect("acquire segment") {
                 info!("acquire segment for reader r2 {:?}", slice);
                 while let Some(event) = slice.next() {
                     assert_eq!(
@@ -172,7 +183,7 @@ fn test_multi_reader_multi_segments_tail_read(client_factory: &ClientFactory, rt
                     let prev = read_count2.fetch_add(1, Ordering::SeqCst);
                     info!("read count {}", prev + 1);
                 }
-                reader2.release_segment(slice).await;
+                reader2.release_segment(slice).await.expect("release segment");
             }
         }
     });
@@ -214,16 +225,17 @@ async fn test_release_segment(client_factory: &ClientFactory) {
 
     let rg: ReaderGroup = client_factory
         .create_reader_group(scope_name, "rg-release".to_string(), stream)
-        .await;
-    let mut reader = rg.create_reader("r1".to_string()).await;
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
 
     let mut event_count = 0;
     let mut release_invoked = false;
     while event_count < NUM_EVENTS {
-        if let Some(mut slice) = reader.acquire_segment().await {
+        if let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
             loop {
                 if !release_invoked && event_count == 5 {
-                    reader.release_segment(slice).await;
+                    reader.release_segment(slice).await.expect("release segment");
                     release_invoked = true;
                     break;
                 } else if let Some(event) = slice.next() {
@@ -278,8 +290,9 @@ async fn test_release_segment_at(client_factory: &ClientFactory) {
 
     let rg = client_factory
         .create_reader_group(scope_name, "rg-release-segment".to_string(), str)
-        .await;
-    let mut reader = rg.create_reader("r1".to_string()).await;
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
     let mut event_count = 0;
     let mut release_invoked = false;
     loop {
@@ -287,10 +300,13 @@ async fn test_release_segment_at(client_factory: &ClientFactory) {
             // all events have been read. Exit test.
             break;
         }
-        if let Some(mut slice) = reader.acquire_segment().await {
+        if let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
             loop {
                 if !release_invoked && event_count == 5 {
-                    reader.release_segment_at(slice, 0).await; // release segment @ the beginning, so that the reader reads all the data.
+                    reader
+                        .release_segment_at(slice, 0)
+                        .await
+                        .expect("release segment"); // release segment @ the beginning, so that the reader reads all the data.
                     release_invoked = true;
                     break;
                 } else if let Some(event) = slice.next() {
@@ -345,15 +361,16 @@ async fn test_stream_scaling(client_factory: &ClientFactory) {
 
     let rg = client_factory
         .create_reader_group(scope_name, "rg_stream_scaling".to_string(), str)
-        .await;
-    let mut reader = rg.create_reader("r1".to_string()).await;
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
     let mut event_count = 0;
     loop {
         if event_count == NUM_EVENTS + NUM_EVENTS {
             // all events have been read. Exit test.
             break;
         }
-        if let Some(mut slice) = reader.acquire_segment().await {
+        if let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
             loop {
                 if let Some(event) = slice.next() {
                     assert_eq!(
@@ -410,10 +427,11 @@ async fn test_read_api(client_factory: &ClientFactory) {
     };
     let rg = client_factory
         .create_reader_group(scope_name, "rg-read-api".to_string(), str)
-        .await;
-    let mut reader = rg.create_reader("r1".to_string()).await;
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
     let mut event_count = 0;
-    while let Some(mut slice) = reader.acquire_segment().await {
+    while let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
         loop {
             if let Some(event) = slice.next() {
                 assert_eq!(
@@ -421,6 +439,11 @@ async fn test_read_api(client_factory: &ClientFactory) {
                     event.value.as_slice(),
                     "Corrupted event read"
                 );
+                let fetched = client_factory
+                    .fetch_event(&event.event_pointer)
+                    .await
+                    .expect("fetch event using event pointer");
+                assert_eq!(event.value, fetched, "Corrupted event fetched");
                 event_count += 1;
             } else {
                 println!(
@@ -471,13 +494,19 @@ fn test_multiple_readers(client_factory: &ClientFactory) {
         }
     });
 
-    let rg = h.block_on(client_factory.create_reader_group(scope_name, "rg_multi_reader".to_string(), str));
+    let rg = h
+        .block_on(client_factory.create_reader_group(scope_name, "rg_multi_reader".to_string(), str))
+        .expect("create reader group");
     // reader 1 will be assigned all the segments.
-    let mut reader1 = h.block_on(rg.create_reader("r1".to_string()));
+    let mut reader1 = h
+        .block_on(rg.create_reader("r1".to_string()))
+        .expect("create reader");
     // no segments will be assigned to reader2
-    let mut reader2 = h.block_on(rg.create_reader("r2".to_string()));
+    let mut reader2 = h
+        .block_on(rg.create_reader("r2".to_string()))
+        .expect("create reader");
 
-    if let Some(mut slice) = h.block_on(reader1.acquire_segment()) {
+    if let Some(mut slice) = h.block_on(reader1.acquire_segment()).expect("acquire segment") {
         if let Some(event) = slice.next() {
             assert_eq!(
                 vec![1; EVENT_SIZE],
@@ -486,20 +515,22 @@ fn test_multiple_readers(client_factory: &ClientFactory) {
             );
             // wait for release timeout.
             thread::sleep(Duration::from_secs(20));
-            h.block_on(reader1.release_segment(slice));
+            h.block_on(reader1.release_segment(slice))
+                .expect("release segment");
         } else {
             panic!("A valid slice is expected");
         }
     }
 
-    if let Some(mut slice) = h.block_on(reader2.acquire_segment()) {
+    if let Some(mut slice) = h.block_on(reader2.acquire_segment()).expect("acquire segment") {
         if let Some(event) = slice.next() {
             assert_eq!(
                 vec![1; EVENT_SIZE],
                 event.value.as_slice(),
                 "Corrupted event read"
             );
-            h.block_on(reader2.release_segment(slice));
+            h.block_on(reader2.release_segment(slice))
+                .expect("release segment");
         } else {
             panic!("A valid slice is expected for reader2");
         }
@@ -539,19 +570,24 @@ fn test_segment_rebalance(client_factory: &ClientFactory) {
         }
     });
 
-    let rg =
-        h.block_on(client_factory.create_reader_group(scope_name, "rg_reblance_reader".to_string(), str));
+    let rg = h
+        .block_on(client_factory.create_reader_group(scope_name, "rg_reblance_reader".to_string(), str))
+        .expect("create reader group");
     // reader 1 will be assigned all the segments.
-    let mut reader1 = h.block_on(rg.create_reader("r1".to_string()));
+    let mut reader1 = h
+        .block_on(rg.create_reader("r1".to_string()))
+        .expect("create reader");
     // no segments will be assigned to reader2 until a rebalance
-    let mut reader2 = h.block_on(rg.create_reader("r2".to_string()));
+    let mut reader2 = h
+        .block_on(rg.create_reader("r2".to_string()))
+        .expect("create reader");
 
     // change the last seg acquire and release time to ensure segment balance is triggered.
     let last_acquire_release_time = Instant::now() - Duration::from_secs(20);
     reader1.set_last_acquire_release_time(last_acquire_release_time);
     reader2.set_last_acquire_release_time(last_acquire_release_time);
     let mut events_read = 0;
-    if let Some(mut slice) = h.block_on(reader1.acquire_segment()) {
+    if let Some(mut slice) = h.block_on(reader1.acquire_segment()).expect("acquire segment") {
         if let Some(event) = slice.next() {
             assert_eq!(
                 vec![1; EVENT_SIZE],
@@ -560,14 +596,15 @@ fn test_segment_rebalance(client_factory: &ClientFactory) {
             );
             events_read += 1;
             // this should trigger a release.
-            h.block_on(reader1.release_segment(slice));
+            h.block_on(reader1.release_segment(slice))
+                .expect("release segment");
         } else {
             panic!("A valid slice is expected");
         }
     }
 
     // try acquiring a segment on reader 2 and verify segments are acquired.
-    if let Some(mut slice) = h.block_on(reader2.acquire_segment()) {
+    if let Some(mut slice) = h.block_on(reader2.acquire_segment()).expect("acquire segment") {
         if let Some(event) = slice.next() {
             // validate that reader 2 acquired a segment.
             assert_eq!(
@@ -576,7 +613,8 @@ fn test_segment_rebalance(client_factory: &ClientFactory) {
                 "Corrupted event read"
             );
             events_read += 1;
-            h.block_on(reader2.release_segment(slice));
+            h.block_on(reader2.release_segment(slice))
+                .expect("release segment");
         } else {
             panic!("A valid slice is expected for reader2");
         }
@@ -586,7 +624,7 @@ fn test_segment_rebalance(client_factory: &ClientFactory) {
     //reset the time to ensure reader1 acquires segment in the next cycle.
     reader1.set_last_acquire_release_time(Instant::now() - Duration::from_secs(20));
 
-    while let Some(slice) = h.block_on(reader1.acquire_segment()) {
+    while let Some(slice) = h.block_on(reader1.acquire_segment()).expect("acquire segment") {
         // read all events in the slice.
         for event in slice {
             assert_eq!(
@@ -637,13 +675,17 @@ fn test_reader_offline(client_factory: &ClientFactory) {
         }
     });
 
-    let rg = h.block_on(client_factory.create_reader_group(scope_name, "rg_reader_offline".to_string(), str));
+    let rg = h
+        .block_on(client_factory.create_reader_group(scope_name, "rg_reader_offline".to_string(), str))
+        .expect("create reader group");
     // reader 1 will be assigned all the segments.
-    let mut reader1 = h.block_on(rg.create_reader("r1".to_string()));
+    let mut reader1 = h
+        .block_on(rg.create_reader("r1".to_string()))
+        .expect("create reader");
 
     // read one event using reader1 and release it back.
     // A drop of segment slice does the same .
-    if let Some(mut slice) = h.block_on(reader1.acquire_segment()) {
+    if let Some(mut slice) = h.block_on(reader1.acquire_segment()).expect("acquire segment") {
         if let Some(event) = slice.next() {
             assert_eq!(
                 vec![1; EVENT_SIZE],
@@ -652,7 +694,8 @@ fn test_reader_offline(client_factory: &ClientFactory) {
             );
             // wait for release timeout.
             thread::sleep(Duration::from_secs(10));
-            h.block_on(reader1.release_segment(slice));
+            h.block_on(reader1.release_segment(slice))
+                .expect("release segment");
         } else {
             panic!("A valid slice is expected");
         }
@@ -660,10 +703,12 @@ fn test_reader_offline(client_factory: &ClientFactory) {
     // reader offline.
     h.block_on(reader1.reader_offline());
 
-    let mut reader2 = h.block_on(rg.create_reader("r2".to_string()));
+    let mut reader2 = h
+        .block_on(rg.create_reader("r2".to_string()))
+        .expect("create reader");
 
     let mut events_read = 1; // one event has been already read by reader 1.
-    while let Some(slice) = h.block_on(reader2.acquire_segment()) {
+    while let Some(slice) = h.block_on(reader2.acquire_segment()).expect("acquire segment") {
         // read from a Segment slice.
         for event in slice {
             assert_eq!(
@@ -680,6 +725,69 @@ fn test_reader_offline(client_factory: &ClientFactory) {
     assert_eq!(NUM_EVENTS, events_read);
 }
 
+async fn test_reader_group_delete(client_factory: &ClientFactory) {
+    let scope_name = Scope::from("testScope".to_owned());
+    let stream_name = Stream::from("testReaderGroupDelete".to_owned());
+    let str = ScopedStream {
+        scope: scope_name.clone(),
+        stream: stream_name.clone(),
+    };
+    const NUM_EVENTS: usize = 10;
+    const EVENT_SIZE: usize = 10;
+
+    let new_stream = create_scope_stream(
+        client_factory.get_controller_client(),
+        &scope_name,
+        &stream_name,
+        1,
+    )
+    .await;
+    // write events only if the stream is created.
+    if new_stream {
+        write_events(
+            scope_name.clone(),
+            stream_name.clone(),
+            client_factory.clone(),
+            NUM_EVENTS,
+            EVENT_SIZE,
+        )
+        .await;
+    }
+
+    let rg = client_factory
+        .create_reader_group(scope_name.clone(), "rg_delete".to_string(), str.clone())
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
+    if let Some(mut slice) = reader.acquire_segment().await.expect("acquire segment") {
+        assert!(slice.next().is_some(), "A valid event is expected");
+    }
+    rg.delete().await.expect("delete reader group");
+
+    // a reader group created with the same name starts from scratch.
+    let rg = client_factory
+        .create_reader_group(scope_name, "rg_delete".to_string(), str)
+        .await
+        .expect("create reader group");
+    let mut reader = rg.create_reader("r1".to_string()).await.expect("create reader");
+    let mut events_read = 0;
+    while let Some(slice) = reader.acquire_segment().await.expect("acquire segment") {
+        for event in slice {
+            assert_eq!(
+                vec![1; EVENT_SIZE],
+                event.value.as_slice(),
+                "Corrupted event read"
+            );
+            events_read += 1;
+        }
+        if events_read == NUM_EVENTS {
+            break;
+        }
+    }
+    assert_eq!(NUM_EVENTS, events_read);
+    reader.reader_offline().await;
+}
+
 // helper method to write events to Pravega
 async fn write_events(
     scope_name: Scope,
@@ -367,7 +367,7 @@ pub struct StreamConfiguration {
     pub retention: Retention,
 }
 
-#[derive(new, Debug, Clone)]
+#[derive(new, Debug, Clone, PartialEq, Eq)]
 pub struct StreamCut {
     pub scoped_stream: ScopedStream,
     pub segment_offset_map: HashMap<i64, i64>,
@@ -0,0 +1,208 @@
+//
+// Copyright (c) Dell Inc., or its subsidiaries. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+
+use pravega_client_shared::ScopedSegment;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+///
+/// AckTracker keeps track of the events handed out by an EventReader which have not been
+/// acknowledged yet. Events can be acknowledged in any order, the reader only publishes the offset
+/// of the lowest un-acknowledged event of a segment to the reader group state. This ensures that
+/// events which have not been processed are delivered again if the reader crashes.
+///
+#[derive(Debug, Default)]
+pub(crate) struct AckTracker {
+    // maps the start offset of every pending event of a segment to the token of its ack handle.
+    pending: Mutex<HashMap<ScopedSegment, BTreeMap<i64, u64>>>,
+    next_token: AtomicU64,
+}
+
+impl AckTracker {
+    //
+    // Register an event which is handed out and return the handle used to acknowledge it.
+    //
+    pub(crate) fn register(self: &Arc<Self>, segment: ScopedSegment, offset: i64) -> AckHandle {
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+        self.pending
+            .lock()
+            .expect("acquire ack tracker lock")
+            .entry(segment.clone())
+            .or_default()
+            .insert(offset, token);
+        AckHandle {
+            segment,
+            offset,
+            token,
+            tracker: self.clone(),
+        }
+    }
+
+    fn ack(&self, segment: &ScopedSegment, offset: i64, token: u64) {
+        let mut pending = self.pending.lock().expect("acquire ack tracker lock");
+        if let Some(offsets) = pending.get_mut(segment) {
+            // the event may have been handed out again since this handle was created.
+            if offsets.get(&offset) == Some(&token) {
+                offsets.remove(&offset);
+            }
+            if offsets.is_empty() {
+                pending.remove(segment);
+            }
+        }
+    }
+
+    //
+    // Return the offset of the lowest un-acknowledged event of the segment.
+    //
+    pub(crate) fn lowest_pending(&self, segment: &ScopedSegment) -> Option<i64> {
+        self.pending
+            .lock()
+            .expect("acquire ack tracker lock")
+            .get(segment)
+            .and_then(|offsets| offsets.keys().next().copied())
+    }
+
+    //
+    // Return the offset which can be published for the segment given the offset the reader has
+    // read up to.
+    //
+    pub(crate) fn committed_offset(&self, segment: &ScopedSegment, read_offset: i64) -> i64 {
+        self.lowest_pending(segment)
+            .map_or(read_offset, |offset| offset.min(read_offset))
+    }
+
+    //
+    // Forget the pending events of the segment at or after the given offset, they are going to be
+    // delivered again.
+    //
+    pub(crate) fn forget_from(&self, segment: &ScopedSegment, offset: i64) {
+        let mut pending = self.pending.lock().expect("acquire ack tracker lock");
+        if let Some(offsets) = pending.get_mut(segment) {
+            offsets.split_off(&offset);
+            if offsets.is_empty() {
+                pending.remove(segment);
+            }
+        }
+    }
+
+    //
+    // Forget the pending events of the segment, this is used when the segment is no longer owned
+    // by the reader. Acknowledging those events afterwards has no effect.
+    //
+    pub(crate) fn forget(&self, segment: &ScopedSegment) {
+        debug!("forget pending events of segment {:?}", segment);
+        self.pending
+            .lock()
+            .expect("acquire ack tracker lock")
+            .remove(segment);
+    }
+
+    pub(crate) fn forget_all(&self) {
+        self.pending.lock().expect("acquire ack tracker lock").clear();
+    }
+}
+
+///
+/// The handle used to acknowledge that an event has been processed, see [`ReaderConfigBuilder::set_ack_tracking`].
+/// An event whose handle is dropped without being acknowledged stays pending, which prevents the
+/// reader from publishing any offset past it.
+///
+/// [`ReaderConfigBuilder::set_ack_tracking`]: crate::reader_group_config::ReaderConfigBuilder::set_ack_tracking
+///
+pub struct AckHandle {
+    segment: ScopedSegment,
+    offset: i64,
+    token: u64,
+    tracker: Arc<AckTracker>,
+}
+
+impl AckHandle {
+    ///
+    /// Acknowledge that the event has been processed.
+    ///
+    pub fn ack(self) {
+        self.tracker.ack(&self.segment, self.offset, self.token);
+    }
+
+    ///
+    /// Return the segment of the event.
+    ///
+    pub fn get_segment(&self) -> &ScopedSegment {
+        &self.segment
+    }
+
+    ///
+    /// Return the offset of the event in its segment.
+    ///
+    pub fn get_offset(&self) -> i64 {
+        self.offset
+    }
+}
+
+impl fmt::Debug for AckHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AckHandle")
+            .field("segment", &self.segment)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_order_acks() {
+        let tracker = Arc::new(AckTracker::default());
+        let segment = ScopedSegment::from("scope/stream/0.#epoch.0");
+        let handle0 = tracker.register(segment.clone(), 0);
+        let handle1 = tracker.register(segment.clone(), 10);
+        let handle2 = tracker.register(segment.clone(), 20);
+        assert_eq!(tracker.committed_offset(&segment, 30), 0);
+
+        handle1.ack();
+        assert_eq!(tracker.committed_offset(&segment, 30), 0);
+        handle0.ack();
+        assert_eq!(tracker.committed_offset(&segment, 30), 20);
+        handle2.ack();
+        assert_eq!(tracker.committed_offset(&segment, 30), 30);
+        assert_eq!(tracker.lowest_pending(&segment), None);
+    }
+
+    #[test]
+    fn test_forget() {
+        let tracker = Arc::new(AckTracker::default());
+        let segment = ScopedSegment::from("scope/stream/0.#epoch.0");
+        let handle0 = tracker.register(segment.clone(), 0);
+        let stale = tracker.register(segment.clone(), 10);
+        tracker.forget_from(&segment, 10);
+        assert_eq!(tracker.lowest_pending(&segment), Some(0));
+
+        // the event is delivered again, the stale handle does not acknowledge it.
+        let handle1 = tracker.register(segment.clone(), 10);
+        handle0.ack();
+        stale.ack();
+        assert_eq!(tracker.lowest_pending(&segment), Some(10));
+        handle1.ack();
+        assert_eq!(tracker.lowest_pending(&segment), None);
+
+        let handle = tracker.register(segment.clone(), 20);
+        tracker.forget(&segment);
+        assert_eq!(tracker.committed_offset(&segment, 30), 30);
+        handle.ack();
+        tracker.register(segment.clone(), 30);
+        tracker.forget_all();
+        assert_eq!(tracker.lowest_pending(&segment), None);
+    }
+}
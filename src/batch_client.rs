@@ -0,0 +1,512 @@
+//
+// Copyright (c) Dell Inc., or its subsidiaries. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+
+use crate::client_factory::ClientFactory;
+use crate::segment_metadata::SegmentMetadataClientError;
+use crate::segment_reader::{AsyncSegmentReader, AsyncSegmentReaderImpl, ReaderError};
+use bytes::BytesMut;
+use pravega_client_retry::retry_result::RetryError;
+use pravega_client_shared::{ScopedSegment, ScopedStream, StreamCut};
+use pravega_controller_client::ControllerError;
+use pravega_wire_protocol::commands::{Command, EventCommand, TYPE_PLUS_LENGTH_SIZE};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::collections::HashMap;
+use tracing::debug;
+
+const READ_BUFFER_SIZE: i32 = 1024 * 1024;
+
+#[derive(Debug, Snafu)]
+pub enum BatchClientError {
+    #[snafu(display("Controller error while performing {}: {:?}", error_msg, err))]
+    ControllerRequestError {
+        error_msg: String,
+        err: RetryError<ControllerError>,
+    },
+    #[snafu(display("Segment metadata error while performing {}: {}", error_msg, source))]
+    SegmentMetadataError {
+        error_msg: String,
+        source: SegmentMetadataClientError,
+    },
+    #[snafu(display("Invalid stream cuts: {}", error_msg))]
+    InvalidStreamCuts { error_msg: String },
+}
+
+///
+/// A range of a segment that can be read independently of the other ranges, the range starts at
+/// `start_offset` inclusive and ends at `end_offset` exclusive. Segment ranges are serializable
+/// so that they can be handed over to separate workers.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SegmentRange {
+    pub segment: ScopedSegment,
+    pub start_offset: i64,
+    pub end_offset: i64,
+}
+
+///
+/// BatchClient reads a bounded range of a stream in parallel. Unlike the EventReader, it does not
+/// coordinate with other readers through a reader group, instead it splits the range into segment
+/// ranges which can be read by separate workers in any order.
+///
+/// ```no_run
+/// use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+/// use pravega_client::client_factory::ClientFactory;
+/// use pravega_client_shared::{ScopedStream, StreamCut};
+/// use std::collections::HashMap;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = ClientConfigBuilder::default()
+///         .controller_uri(MOCK_CONTROLLER_URI)
+///         .build()
+///         .expect("creating config");
+///     let client_factory = ClientFactory::new(config);
+///     let batch_client = client_factory.create_batch_client();
+///     let stream = ScopedStream::from("scope/stream");
+///     // stream cuts without segments point to the head and the tail of the stream.
+///     let ranges = batch_client
+///         .get_segment_ranges(
+///             &StreamCut::new(stream.clone(), HashMap::new()),
+///             &StreamCut::new(stream, HashMap::new()),
+///         )
+///         .await
+///         .expect("get segment ranges");
+///     for range in ranges {
+///         let mut iter = batch_client.read_segment_range(&range).await;
+///         while let Some(event) = iter.next().await {
+///             let event = event.expect("read event");
+///         }
+///     }
+/// }
+/// ```
+///
+pub struct BatchClient {
+    factory: ClientFactory,
+}
+
+impl BatchClient {
+    pub(crate) fn new(factory: ClientFactory) -> Self {
+        BatchClient { factory }
+    }
+
+    ///
+    /// Get the segment ranges of a stream between two stream cuts. A `from` stream cut without
+    /// segments points to the head of the stream and a `to` stream cut without segments points to
+    /// the current tail of the stream. Both stream cuts must belong to the same stream.
+    ///
+    pub async fn get_segment_ranges(
+        &self,
+        from: &StreamCut,
+        to: &StreamCut,
+    ) -> Result<Vec<SegmentRange>, BatchClientError> {
+        if from.scoped_stream != to.scoped_stream {
+            return Err(BatchClientError::InvalidStreamCuts {
+                error_msg: format!(
+                    "stream cuts belong to different streams {:?} and {:?}",
+                    from.scoped_stream, to.scoped_stream
+                ),
+            });
+        }
+        let stream = &from.scoped_stream;
+        let from = if from.segment_offset_map.is_empty() {
+            self.get_head_stream_cut(stream).await?
+        } else {
+            from.clone()
+        };
+        let to = if to.segment_offset_map.is_empty() {
+            self.get_tail_stream_cut(stream).await?
+        } else {
+            to.clone()
+        };
+
+        let segments = self
+            .factory
+            .get_controller_client()
+            .get_segments_between(&from, &to)
+            .await
+            .map_err(|e| BatchClientError::ControllerRequestError {
+                error_msg: format!("get segments of stream {:?} between stream cuts", stream),
+                err: e,
+            })?;
+        let mut ranges = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let number = segment.segment.number;
+            let start_offset = from.segment_offset_map.get(&number).copied().unwrap_or(0);
+            let end_offset = match to.segment_offset_map.get(&number) {
+                Some(offset) => *offset,
+                // segments before the end stream cut are sealed, read them completely.
+                None => self.fetch_segment_length(&segment).await?,
+            };
+            ranges.push(SegmentRange {
+                segment,
+                start_offset,
+                end_offset,
+            });
+        }
+        debug!("segment ranges of stream {:?}: {:?}", stream, ranges);
+        Ok(ranges)
+    }
+
+    ///
+    /// Create an iterator over the events of a segment range. The reads of the iterator carry the
+    /// delegation token of the stream of the range.
+    ///
+    pub async fn read_segment_range(&self, range: &SegmentRange) -> SegmentIterator {
+        let token_provider = self
+            .factory
+            .create_delegation_token_provider(ScopedStream::from(&range.segment))
+            .await;
+        let reader =
+            AsyncSegmentReaderImpl::init(range.segment.clone(), self.factory.clone(), token_provider).await;
+        SegmentIterator::new(range.clone(), Box::new(reader))
+    }
+
+    async fn get_head_stream_cut(&self, stream: &ScopedStream) -> Result<StreamCut, BatchClientError> {
+        let segments = self
+            .factory
+            .get_controller_client()
+            .get_head_segments(stream)
+            .await
+            .map_err(|e| BatchClientError::ControllerRequestError {
+                error_msg: format!("get head segments of stream {:?}", stream),
+                err: e,
+            })?;
+        Ok(StreamCut::new(
+            stream.clone(),
+            segments
+                .into_iter()
+                .map(|(segment, offset)| (segment.number, offset))
+                .collect(),
+        ))
+    }
+
+    async fn get_tail_stream_cut(&self, stream: &ScopedStream) -> Result<StreamCut, BatchClientError> {
+        let segments = self
+            .factory
+            .get_controller_client()
+            .get_current_segments(stream)
+            .await
+            .map_err(|e| BatchClientError::ControllerRequestError {
+                error_msg: format!("get current segments of stream {:?}", stream),
+                err: e,
+            })?;
+        let mut positions = HashMap::new();
+        for segment in segments.get_segments() {
+            let length = self.fetch_segment_length(&segment).await?;
+            positions.insert(segment.segment.number, length);
+        }
+        Ok(StreamCut::new(stream.clone(), positions))
+    }
+
+    async fn fetch_segment_length(&self, segment: &ScopedSegment) -> Result<i64, BatchClientError> {
+        self.factory
+            .create_segment_metadata_client(segment.clone())
+            .await
+            .fetch_current_segment_length()
+            .await
+            .map_err(|e| BatchClientError::SegmentMetadataError {
+                error_msg: format!("fetch length of segment {:?}", segment),
+                source: e,
+            })
+    }
+}
+
+///
+/// An iterator over the events of a segment range. The range must start at an event boundary,
+/// reading stops once the end offset of the range is reached.
+///
+pub struct SegmentIterator {
+    range: SegmentRange,
+    reader: Box<dyn AsyncSegmentReader>,
+    // the offset of the first byte in the buffer.
+    offset: i64,
+    buffer: BytesMut,
+}
+
+impl SegmentIterator {
+    fn new(range: SegmentRange, reader: Box<dyn AsyncSegmentReader>) -> Self {
+        SegmentIterator {
+            offset: range.start_offset,
+            range,
+            reader,
+            buffer: BytesMut::new(),
+        }
+    }
+
+    ///
+    /// Get the offset of the next event to be returned.
+    ///
+    pub fn get_offset(&self) -> i64 {
+        self.offset
+    }
+
+    ///
+    /// Read the next event of the segment range, None is returned once the end of the range is
+    /// reached.
+    ///
+    pub async fn next(&mut self) -> Option<Result<Vec<u8>, ReaderError>> {
+        if self.offset >= self.range.end_offset {
+            return None;
+        }
+        Some(self.read_event().await)
+    }
+
+    async fn read_event(&mut self) -> Result<Vec<u8>, ReaderError> {
+        self.fill_buffer(TYPE_PLUS_LENGTH_SIZE as usize).await?;
+        let mut length_bytes = [0u8; 4];
+        length_bytes.copy_from_slice(&self.buffer[4..TYPE_PLUS_LENGTH_SIZE as usize]);
+        let data_length = i32::from_be_bytes(length_bytes);
+        if data_length < 0 {
+            return Err(ReaderError::OperationError {
+                segment: self.range.segment.to_string(),
+                can_retry: false,
+                operation: "read segment range".to_string(),
+                error_msg: format!("invalid event length {} at offset {}", data_length, self.offset),
+            });
+        }
+        let event_length = TYPE_PLUS_LENGTH_SIZE as usize + data_length as usize;
+        self.fill_buffer(event_length).await?;
+
+        let event_offset = self.offset;
+        let event_bytes = self.buffer.split_to(event_length);
+        self.offset += event_length as i64;
+        let event = EventCommand::read_from(&event_bytes).map_err(|e| ReaderError::OperationError {
+            segment: self.range.segment.to_string(),
+            can_retry: false,
+            operation: "read segment range".to_string(),
+            error_msg: format!("failed to decode event at offset {}: {:?}", event_offset, e),
+        })?;
+        Ok(event.data)
+    }
+
+    // Read from the segment until the buffer holds at least the given number of bytes.
+    async fn fill_buffer(&mut self, size: usize) -> Result<(), ReaderError> {
+        while self.buffer.len() < size {
+            let read_offset = self.offset + self.buffer.len() as i64;
+            if read_offset >= self.range.end_offset {
+                return Err(ReaderError::OperationError {
+                    segment: self.range.segment.to_string(),
+                    can_retry: false,
+                    operation: "read segment range".to_string(),
+                    error_msg: format!(
+                        "event at offset {} exceeds the end offset {} of the range",
+                        self.offset, self.range.end_offset
+                    ),
+                });
+            }
+            let length = std::cmp::min(READ_BUFFER_SIZE as i64, self.range.end_offset - read_offset) as i32;
+            let reply = self.reader.read(read_offset, length).await?;
+            // an empty reply that is not the end of segment means the range reaches past the data
+            // written so far, the read can be retried by calling next again once more data is written.
+            if reply.data.is_empty() {
+                let (can_retry, error_msg) = if reply.end_of_segment {
+                    (
+                        false,
+                        format!("reached the end of segment at offset {}", read_offset),
+                    )
+                } else {
+                    (true, format!("no data available at offset {}", read_offset))
+                };
+                return Err(ReaderError::OperationError {
+                    segment: self.range.segment.to_string(),
+                    can_retry,
+                    operation: "read segment range".to_string(),
+                    error_msg,
+                });
+            }
+            self.buffer.extend_from_slice(&reply.data);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_stream;
+    use async_trait::async_trait;
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use pravega_wire_protocol::commands::SegmentReadCommand;
+    use std::io::Write;
+    use tokio::runtime::Runtime;
+
+    // A segment reader that returns at most 5 bytes of the given data per read, the end of
+    // segment is only reported when the segment is sealed.
+    struct MockSegmentReader {
+        data: Vec<u8>,
+        sealed: bool,
+    }
+
+    #[async_trait]
+    impl AsyncSegmentReader for MockSegmentReader {
+        async fn read(&self, offset: i64, length: i32) -> Result<SegmentReadCommand, ReaderError> {
+            let start = std::cmp::min(offset as usize, self.data.len());
+            let end = std::cmp::min(start + std::cmp::min(length as usize, 5), self.data.len());
+            Ok(SegmentReadCommand {
+                segment: "scope/stream/0.#epoch.0".to_string(),
+                offset,
+                at_tail: end == self.data.len(),
+                end_of_segment: self.sealed && end == self.data.len(),
+                data: self.data[start..end].to_vec(),
+                request_id: 0,
+            })
+        }
+    }
+
+    fn events(count: u8) -> Vec<u8> {
+        let mut data = vec![];
+        for i in 0..count {
+            let event = EventCommand { data: vec![i; 10] };
+            data.extend(event.write_fields().expect("serialize event"));
+        }
+        data
+    }
+
+    #[test]
+    fn test_get_segment_ranges() {
+        let client_factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let rt = client_factory.get_runtime();
+        rt.block_on(create_stream(&client_factory, "scope", "stream"));
+        let segment = ScopedSegment::from("scope/stream/0.#epoch.0");
+        let mut writer = client_factory.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 100]).expect("write to segment");
+        writer.flush().expect("flush segment");
+
+        let batch_client = client_factory.create_batch_client();
+        let stream = ScopedStream::from("scope/stream");
+        let unbounded = StreamCut::new(stream.clone(), HashMap::new());
+        let ranges = rt
+            .block_on(batch_client.get_segment_ranges(&unbounded, &unbounded))
+            .expect("get segment ranges");
+        assert_eq!(
+            ranges,
+            vec![SegmentRange {
+                segment: segment.clone(),
+                start_offset: 0,
+                end_offset: 100
+            }]
+        );
+
+        let mut offsets = HashMap::new();
+        offsets.insert(0, 40);
+        let from = StreamCut::new(stream.clone(), offsets);
+        let ranges = rt
+            .block_on(batch_client.get_segment_ranges(&from, &unbounded))
+            .expect("get segment ranges");
+        assert_eq!(ranges[0].start_offset, 40);
+
+        // the stream cuts must belong to the same stream.
+        let other = StreamCut::new(ScopedStream::from("scope/other"), HashMap::new());
+        assert!(rt
+            .block_on(batch_client.get_segment_ranges(&unbounded, &other))
+            .is_err());
+    }
+
+    #[test]
+    fn test_segment_iterator() {
+        let rt = Runtime::new().expect("create runtime");
+        let data = events(3);
+        // each event takes 18 bytes, skip the first event.
+        let range = SegmentRange {
+            segment: ScopedSegment::from("scope/stream/0.#epoch.0"),
+            start_offset: 18,
+            end_offset: data.len() as i64,
+        };
+        let mut iter = SegmentIterator::new(range, Box::new(MockSegmentReader { data, sealed: true }));
+        rt.block_on(async {
+            assert_eq!(
+                iter.next().await.expect("event").expect("read event"),
+                vec![1; 10]
+            );
+            assert_eq!(iter.get_offset(), 36);
+            assert_eq!(
+                iter.next().await.expect("event").expect("read event"),
+                vec![2; 10]
+            );
+            assert!(iter.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn test_segment_iterator_partial_event() {
+        let rt = Runtime::new().expect("create runtime");
+        let data = events(2);
+        // the range ends in the middle of the second event.
+        let range = SegmentRange {
+            segment: ScopedSegment::from("scope/stream/0.#epoch.0"),
+            start_offset: 0,
+            end_offset: 20,
+        };
+        let mut iter = SegmentIterator::new(range, Box::new(MockSegmentReader { data, sealed: true }));
+        rt.block_on(async {
+            assert_eq!(
+                iter.next().await.expect("event").expect("read event"),
+                vec![0; 10]
+            );
+            assert!(iter.next().await.expect("event").is_err());
+        });
+    }
+
+    #[test]
+    fn test_segment_iterator_negative_length() {
+        let rt = Runtime::new().expect("create runtime");
+        // an event header with a negative length.
+        let mut data = EventCommand::TYPE_CODE.to_be_bytes().to_vec();
+        data.extend_from_slice(&(-1i32).to_be_bytes());
+        data.extend_from_slice(&[0; 10]);
+        let range = SegmentRange {
+            segment: ScopedSegment::from("scope/stream/0.#epoch.0"),
+            start_offset: 0,
+            end_offset: data.len() as i64,
+        };
+        let mut iter = SegmentIterator::new(range, Box::new(MockSegmentReader { data, sealed: true }));
+        rt.block_on(async {
+            assert!(iter.next().await.expect("event").is_err());
+            assert_eq!(iter.get_offset(), 0);
+        });
+    }
+
+    #[test]
+    fn test_segment_iterator_empty_read() {
+        let rt = Runtime::new().expect("create runtime");
+        let data = events(2);
+        // the range ends after the data written so far and the segment is not sealed.
+        let range = SegmentRange {
+            segment: ScopedSegment::from("scope/stream/0.#epoch.0"),
+            start_offset: 0,
+            end_offset: 54,
+        };
+        let mut iter = SegmentIterator::new(range, Box::new(MockSegmentReader { data, sealed: false }));
+        rt.block_on(async {
+            assert_eq!(
+                iter.next().await.expect("event").expect("read event"),
+                vec![0; 10]
+            );
+            assert_eq!(
+                iter.next().await.expect("event").expect("read event"),
+                vec![1; 10]
+            );
+            match iter.next().await.expect("event") {
+                Err(ReaderError::OperationError { can_retry, .. }) => assert!(can_retry),
+                other => panic!("unexpected result {:?}", other),
+            }
+            assert_eq!(iter.get_offset(), 36);
+        });
+    }
+}
@@ -13,17 +13,21 @@ use pravega_client_shared::{DelegationToken, PravegaNodeUri, Scope, ScopedSegmen
 use pravega_connection_pool::connection_pool::ConnectionPool;
 use pravega_controller_client::mock_controller::MockController;
 use pravega_controller_client::{ControllerClient, ControllerClientImpl};
+use pravega_wire_protocol::commands::{Command, EventCommand};
 use pravega_wire_protocol::connection_factory::{
     ConnectionFactory, ConnectionFactoryConfig, SegmentConnectionManager,
 };
 
+use crate::batch_client::BatchClient;
 use crate::byte_stream::{ByteStreamReader, ByteStreamWriter};
 use crate::event_reader_group::ReaderGroup;
 use crate::event_stream_writer::EventStreamWriter;
 use crate::raw_client::RawClientImpl;
+use crate::reader_group::reader_group_state::{ReaderGroupState, ReaderGroupStateError};
 use crate::reader_group_config::{ReaderGroupConfig, ReaderGroupConfigBuilder};
 use crate::segment_metadata::SegmentMetadataClient;
-use crate::segment_reader::AsyncSegmentReaderImpl;
+use crate::segment_reader::{AsyncSegmentReader, AsyncSegmentReaderImpl, ReaderError};
+use crate::stream::event_pointer::EventPointerVersioned;
 use crate::table_synchronizer::TableSynchronizer;
 use crate::tablemap::TableMap;
 use crate::transaction::transactional_event_stream_writer::TransactionalEventStreamWriter;
@@ -112,7 +116,7 @@ impl ClientFactory {
         scope: Scope,
         reader_group_name: String,
         stream: ScopedStream,
-    ) -> ReaderGroup {
+    ) -> Result<ReaderGroup, ReaderGroupStateError> {
         info!(
             "Creating reader group {:?} to read data from stream {:?}",
             reader_group_name, stream
@@ -126,11 +130,26 @@ impl ClientFactory {
         scope: Scope,
         reader_group_name: String,
         rg_config: ReaderGroupConfig,
-    ) -> ReaderGroup {
+    ) -> Result<ReaderGroup, ReaderGroupStateError> {
         info!("Creating reader group {:?} ", reader_group_name);
         ReaderGroup::create(scope, reader_group_name, rg_config, self.clone()).await
     }
 
+    pub async fn delete_reader_group(
+        &self,
+        scope: Scope,
+        reader_group_name: String,
+    ) -> Result<(), ReaderGroupStateError> {
+        info!("Deleting reader group {:?} ", reader_group_name);
+        let sync = TableSynchronizer::open(scope, reader_group_name, self.clone())
+            .await
+            .map_err(|source| ReaderGroupStateError::TableMapError {
+                error_msg: "open reader group state table".to_owned(),
+                source,
+            })?;
+        ReaderGroupState::from_synchronizer(sync).delete().await
+    }
+
     pub async fn create_transactional_event_stream_writer(
         &self,
         stream: ScopedStream,
@@ -151,6 +170,10 @@ impl ClientFactory {
         )
     }
 
+    pub fn create_batch_client(&self) -> BatchClient {
+        BatchClient::new(self.clone())
+    }
+
     pub async fn create_delegation_token_provider(&self, stream: ScopedStream) -> DelegationTokenProvider {
         self.0.create_delegation_token_provider(stream).await
     }
@@ -159,6 +182,47 @@ impl ClientFactory {
         SegmentMetadataClient::new(segment.clone(), self.clone()).await
     }
 
+    ///
+    /// Fetch a single event given the EventPointer which was returned with the event when it
+    /// was read by an EventReader.
+    ///
+    pub async fn fetch_event(&self, pointer: &EventPointerVersioned) -> Result<Vec<u8>, ReaderError> {
+        let segment = pointer.get_segment().clone();
+        let reader = self.create_async_event_reader(segment.clone()).await;
+        let length = pointer.get_event_length();
+        let mut offset = pointer.get_event_start_offset();
+        let mut data: Vec<u8> = Vec::with_capacity(length as usize);
+        while data.len() < length as usize {
+            let reply = reader.read(offset, length - data.len() as i32).await?;
+            // an empty reply means the pointer reaches past the data written to the segment,
+            // reading again at the same offset would not make any progress.
+            if reply.data.is_empty() {
+                let error_msg = if reply.end_of_segment {
+                    format!("reached the end of segment before reading {} bytes", length)
+                } else {
+                    format!("no data at offset {} before reading {} bytes", offset, length)
+                };
+                return Err(ReaderError::OperationError {
+                    segment: segment.to_string(),
+                    can_retry: false,
+                    operation: "fetch event".to_string(),
+                    error_msg,
+                });
+            }
+            offset += reply.data.len() as i64;
+            data.extend_from_slice(&reply.data);
+        }
+        // the segment store may return more data than requested.
+        data.truncate(length as usize);
+        let event = EventCommand::read_from(&data).map_err(|e| ReaderError::OperationError {
+            segment: segment.to_string(),
+            can_retry: false,
+            operation: "fetch event".to_string(),
+            error_msg: format!("failed to decode event: {:?}", e),
+        })?;
+        Ok(event.data)
+    }
+
     pub fn get_controller_client(&self) -> &dyn ControllerClient {
         self.0.get_controller_client()
     }
@@ -211,3 +275,84 @@ impl fmt::Debug for ClientFactoryInternal {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::event_pointer::EventPointerV1;
+    use crate::tablemap::TableError;
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use pravega_client_shared::Reader;
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    #[test]
+    fn test_delete_reader_group() {
+        let cf = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let scope = Scope::from("scope".to_string());
+        let name = "rg".to_string();
+        let config = ReaderGroupConfigBuilder::default()
+            .add_stream(ScopedStream::from("scope/stream"))
+            .build();
+        cf.get_runtime().block_on(async {
+            let mut state =
+                ReaderGroupState::new(scope.clone(), name.clone(), &cf, config.config, HashMap::new()).await;
+            state
+                .add_reader(&Reader::from("r1".to_string()))
+                .await
+                .expect("add reader");
+
+            // the online reader is put offline and the table segment is deleted.
+            cf.delete_reader_group(scope.clone(), name.clone())
+                .await
+                .expect("delete reader group");
+            let mut sync = TableSynchronizer::open(scope.clone(), name.clone(), cf.clone())
+                .await
+                .expect("open table synchronizer");
+            assert!(matches!(
+                sync.fetch_updates().await,
+                Err(TableError::TableDoesNotExist { .. })
+            ));
+
+            // deleting a reader group which does not exist does not create it.
+            cf.delete_reader_group(scope.clone(), name.clone())
+                .await
+                .expect("delete missing reader group");
+            assert!(matches!(
+                sync.fetch_updates().await,
+                Err(TableError::TableDoesNotExist { .. })
+            ));
+        });
+    }
+
+    #[test]
+    fn test_fetch_event_past_written_data() {
+        let cf = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        cf.get_runtime()
+            .block_on(crate::create_stream(&cf, "scope", "stream"));
+        let segment = ScopedSegment::from("scope/stream/0.#epoch.0");
+        let mut writer = cf.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 100]).expect("write");
+        writer.flush().expect("flush");
+
+        // the pointer reaches past the 100 bytes written to the segment.
+        let pointer = EventPointerVersioned::V1(EventPointerV1::new(segment, 0, 150));
+        let result = cf.get_runtime().block_on(cf.fetch_event(&pointer));
+        assert!(matches!(result, Err(ReaderError::OperationError { .. })));
+    }
+}
@@ -0,0 +1,463 @@
+//
+// Copyright (c) Dell Inc., or its subsidiaries. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+
+use crate::client_factory::ClientFactory;
+use crate::error::*;
+use crate::event_reader::EventReader;
+use crate::event_stream_writer::EventStreamWriter;
+use crate::reader_group::reader_group_state::ReaderGroupStateError;
+use crate::segment_slice::{Event, SegmentSlice};
+use pravega_client_shared::{ScopedSegment, ScopedStream};
+use serde::{Deserialize, Serialize};
+use serde_cbor::from_slice;
+use serde_cbor::to_vec;
+use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
+use std::fmt::Display;
+use tracing::{debug, warn};
+
+#[derive(Debug, Snafu)]
+pub enum DeadLetterError {
+    #[snafu(display("Failed to serialize the dead letter envelope: {}", source))]
+    SerializeEnvelope { source: SerdeError },
+    #[snafu(display("Failed to write event to dead letter stream {}: {}", stream, source))]
+    WriteDeadLetter {
+        stream: ScopedStream,
+        source: SegmentWriterError,
+    },
+    #[snafu(display("Write to dead letter stream {} was aborted", stream))]
+    WriteDeadLetterAborted { stream: ScopedStream },
+    #[snafu(display("Failed to release the segment slice to the reader: {}", source))]
+    ReleaseSegment { source: ReaderGroupStateError },
+}
+
+///
+/// The envelope of an event written to a dead letter stream. It records where the event was read
+/// from and the error of its last processing attempt.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeadLetterEnvelope {
+    pub stream: ScopedStream,
+    pub segment: ScopedSegment,
+    pub offset: i64,
+    pub error: String,
+    pub attempts: u32,
+    pub event: Vec<u8>,
+}
+
+impl DeadLetterEnvelope {
+    ///
+    /// Method to serialize the DeadLetterEnvelope into bytes.
+    ///
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerdeError> {
+        let encoded = to_vec(&self).context(Cbor {
+            msg: "serialize DeadLetterEnvelope".to_owned(),
+        })?;
+        Ok(encoded)
+    }
+
+    ///
+    /// Method to de-serialize the DeadLetterEnvelope from bytes.
+    ///
+    pub fn from_bytes(input: &[u8]) -> Result<DeadLetterEnvelope, SerdeError> {
+        let decoded: DeadLetterEnvelope = from_slice(input).context(Cbor {
+            msg: "deserialize DeadLetterEnvelope".to_owned(),
+        })?;
+        Ok(decoded)
+    }
+}
+
+///
+/// DeadLetterQueue moves the events which cannot be processed out of the way of a reader. An event
+/// which fails processing `max_attempts` times, or which cannot be deserialized, is written to the
+/// dead letter stream in a [`DeadLetterEnvelope`] and the reader advances past it.
+///
+/// ```no_run
+/// use pravega_client::client_factory::ClientFactory;
+/// use pravega_client::dead_letter::DeadLetterQueue;
+/// use pravega_client::event_reader::EventReader;
+/// use pravega_client_shared::ScopedStream;
+///
+/// async fn consume(factory: &ClientFactory, mut reader: EventReader) {
+///     let mut dlq = DeadLetterQueue::new(ScopedStream::from("scope/dlq"), 3, factory);
+///     while let Ok(slice) = reader.acquire_segment().await {
+///         if let Some(slice) = slice {
+///             dlq.process_slice(&mut reader, slice, |event| {
+///                 String::from_utf8(event.value.clone()).map(|value| println!("Event read is {}", value))
+///             })
+///             .await
+///             .expect("process segment slice");
+///         }
+///     }
+/// }
+/// ```
+///
+pub struct DeadLetterQueue {
+    stream: ScopedStream,
+    writer: EventStreamWriter,
+    max_attempts: u32,
+    // the number of failed attempts of the events which have not been processed yet.
+    attempts: HashMap<(ScopedSegment, i64), u32>,
+}
+
+impl DeadLetterQueue {
+    ///
+    /// Create a DeadLetterQueue writing to the given stream. This method panics if max_attempts is zero.
+    ///
+    pub fn new(stream: ScopedStream, max_attempts: u32, factory: &ClientFactory) -> Self {
+        assert!(max_attempts > 0, "Max attempts should be positive");
+        DeadLetterQueue {
+            writer: factory.create_event_stream_writer(stream.clone()),
+            stream,
+            max_attempts,
+            attempts: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Record a failed attempt to process the event. Once the event has failed `max_attempts`
+    /// times it is written to the dead letter stream and true is returned, the caller should then
+    /// move on to the next event. Otherwise false is returned and the event should be retried.
+    ///
+    pub async fn record_failure(&mut self, event: &Event, error: &str) -> Result<bool, DeadLetterError> {
+        let key = (event.event_pointer.get_segment().clone(), event.offset_in_segment);
+        let attempts = self.attempts.entry(key.clone()).or_insert(0);
+        *attempts += 1;
+        let attempts = *attempts;
+        if attempts < self.max_attempts {
+            debug!(
+                "event at offset {} of segment {} failed {} times: {}",
+                event.offset_in_segment, key.0, attempts, error
+            );
+            return Ok(false);
+        }
+        self.write_dead_letter(event, error, attempts).await?;
+        self.attempts.remove(&key);
+        Ok(true)
+    }
+
+    ///
+    /// Record that the event has been processed successfully.
+    ///
+    pub fn record_success(&mut self, event: &Event) {
+        self.attempts
+            .remove(&(event.event_pointer.get_segment().clone(), event.offset_in_segment));
+    }
+
+    ///
+    /// Deserialize the value of the event. An event which cannot be deserialized is written to
+    /// the dead letter stream right away and None is returned.
+    ///
+    pub async fn deserialize<T, E, F>(
+        &mut self,
+        event: &Event,
+        deserialize: F,
+    ) -> Result<Option<T>, DeadLetterError>
+    where
+        E: Display,
+        F: FnOnce(&[u8]) -> Result<T, E>,
+    {
+        match deserialize(&event.value) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                let error = format!("failed to deserialize event: {}", e);
+                self.write_dead_letter(event, &error, 1).await?;
+                self.record_success(event);
+                Ok(None)
+            }
+        }
+    }
+
+    ///
+    /// Process the events of a segment slice. An event whose processing fails is read again from
+    /// the reader until it has failed `max_attempts` times, then it is written to the dead letter
+    /// stream and the events after it are processed. The events which are processed or written to
+    /// the dead letter stream are acknowledged if ack tracking is enabled. If the event cannot be
+    /// written to the dead letter stream the error is returned and the event is read again.
+    ///
+    pub async fn process_slice<E, F>(
+        &mut self,
+        reader: &mut EventReader,
+        mut slice: SegmentSlice,
+        mut process: F,
+    ) -> Result<(), DeadLetterError>
+    where
+        E: Display,
+        F: FnMut(&Event) -> Result<(), E>,
+    {
+        while let Some(mut event) = slice.next() {
+            match process(&event) {
+                Ok(()) => self.record_success(&event),
+                Err(e) => {
+                    let offset = event.offset_in_segment;
+                    match self.record_failure(&event, &e.to_string()).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            // read the event again.
+                            reader
+                                .release_segment_at(slice, offset)
+                                .await
+                                .context(ReleaseSegment {})?;
+                            return Ok(());
+                        }
+                        Err(e) => {
+                            // the event is neither processed nor written to the dead letter stream.
+                            reader
+                                .release_segment_at(slice, offset)
+                                .await
+                                .context(ReleaseSegment {})?;
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+            if let Some(ack_handle) = event.ack_handle.take() {
+                ack_handle.ack();
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_dead_letter(
+        &mut self,
+        event: &Event,
+        error: &str,
+        attempts: u32,
+    ) -> Result<(), DeadLetterError> {
+        let segment = event.event_pointer.get_segment().clone();
+        warn!(
+            "writing event at offset {} of segment {} to dead letter stream {}: {}",
+            event.offset_in_segment, segment, self.stream, error
+        );
+        let envelope = DeadLetterEnvelope {
+            stream: ScopedStream::from(&segment),
+            segment: segment.clone(),
+            offset: event.offset_in_segment,
+            error: error.to_string(),
+            attempts,
+            event: event.value.clone(),
+        };
+        let data = envelope.to_bytes().context(SerializeEnvelope {})?;
+        // route the events of a segment to the same segment of the dead letter stream to keep their order.
+        let result = self
+            .writer
+            .write_event_by_routing_key(segment.to_string(), data)
+            .await
+            .await;
+        match result {
+            Ok(res) => res.context(WriteDeadLetter {
+                stream: self.stream.clone(),
+            }),
+            Err(_) => Err(DeadLetterError::WriteDeadLetterAborted {
+                stream: self.stream.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_stream;
+    use crate::reader_group::reader_group_state::MockReaderGroupState;
+    use crate::segment_slice::{SegmentDataBuffer, SliceMetadata};
+    use crate::stream::event_pointer::{EventPointerV1, EventPointerVersioned};
+    use bytes::{BufMut, BytesMut};
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use pravega_client_shared::Reader;
+    use pravega_wire_protocol::commands::{Command, EventCommand};
+    use std::sync::Arc;
+    use tokio::sync::{mpsc, Mutex};
+
+    #[test]
+    fn test_dead_letter_envelope_serde() {
+        let envelope = DeadLetterEnvelope {
+            stream: ScopedStream::from("scope/stream"),
+            segment: ScopedSegment::from("scope/stream/0.#epoch.0"),
+            offset: 10,
+            error: "invalid event".to_string(),
+            attempts: 3,
+            event: vec![1, 2, 3],
+        };
+        let encoded = envelope.to_bytes().expect("encode to byte array");
+        let decoded = DeadLetterEnvelope::from_bytes(&encoded).expect("decode from byte array");
+        assert_eq!(envelope, decoded);
+    }
+
+    #[test]
+    fn test_dead_letter_queue() {
+        let factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let dead_letter_segment = ScopedSegment::from("scope/dlq/0.#epoch.0");
+        factory.get_runtime().block_on(async {
+            create_stream(&factory, "scope", "dlq").await;
+            let mut dlq = DeadLetterQueue::new(ScopedStream::from("scope/dlq"), 2, &factory);
+
+            // the event is written to the dead letter stream once it failed twice.
+            let event = create_event(0, b"event".to_vec());
+            assert!(!dlq
+                .record_failure(&event, "failed")
+                .await
+                .expect("record failure"));
+            assert!(dlq
+                .record_failure(&event, "failed")
+                .await
+                .expect("record failure"));
+            let length = dead_letter_stream_length(&factory, &dead_letter_segment).await;
+            assert!(length > 0);
+
+            // a successful attempt resets the failures of the event.
+            let event = create_event(13, b"event".to_vec());
+            assert!(!dlq
+                .record_failure(&event, "failed")
+                .await
+                .expect("record failure"));
+            dlq.record_success(&event);
+            assert!(!dlq
+                .record_failure(&event, "failed")
+                .await
+                .expect("record failure"));
+
+            // an event which cannot be deserialized is written right away.
+            let event = create_event(26, vec![0xff]);
+            let value = dlq
+                .deserialize(&event, |value| String::from_utf8(value.to_vec()))
+                .await
+                .expect("deserialize event");
+            assert!(value.is_none());
+            assert!(dead_letter_stream_length(&factory, &dead_letter_segment).await > length);
+            let event = create_event(35, b"event".to_vec());
+            let value = dlq
+                .deserialize(&event, |value| String::from_utf8(value.to_vec()))
+                .await
+                .expect("deserialize event");
+            assert_eq!(value, Some("event".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_process_slice() {
+        let factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        // the data of the reader is received from the channel.
+        let reader_factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let segment = ScopedSegment::from("scope/dlqinput/0.#epoch.0");
+        let dead_letter_segment = ScopedSegment::from("scope/dlqretry/0.#epoch.0");
+        factory.get_runtime().block_on(async {
+            create_stream(&factory, "scope", "dlqretry").await;
+            let (tx, rx) = mpsc::channel(1);
+            let mut rg_state = MockReaderGroupState::default();
+            rg_state.expect_get_generation().returning(|| Ok(0u64));
+            rg_state.expect_remove_reader().returning(|_, _| Ok(()));
+            let mut slices = HashMap::new();
+            slices.insert(
+                segment.clone(),
+                SliceMetadata {
+                    scoped_segment: segment.to_string(),
+                    ..Default::default()
+                },
+            );
+            let mut reader = EventReader::init_event_reader(
+                Arc::new(Mutex::new(rg_state)),
+                Reader::from("reader".to_string()),
+                reader_factory.clone(),
+                tx.clone(),
+                rx,
+                slices,
+                HashMap::new(),
+            );
+            let mut dlq = DeadLetterQueue::new(ScopedStream::from("scope/dlqretry"), 3, &factory);
+
+            // the event is read again until it has failed max_attempts times.
+            let mut attempts = 0;
+            let mut processed = Vec::new();
+            while processed.is_empty() {
+                tx.send((
+                    reader.get_read_epoch(&segment),
+                    Ok(create_segment_data(&segment, &[b"poison", b"event"])),
+                ))
+                .await
+                .expect("send segment data");
+                let slice = reader
+                    .acquire_segment()
+                    .await
+                    .expect("acquire segment")
+                    .expect("segment slice");
+                dlq.process_slice(&mut reader, slice, |event| {
+                    if event.value == b"poison" {
+                        attempts += 1;
+                        Err("poison event")
+                    } else {
+                        processed.push(event.value.clone());
+                        Ok(())
+                    }
+                })
+                .await
+                .expect("process segment slice");
+            }
+            assert_eq!(attempts, 3);
+            assert_eq!(processed, vec![b"event".to_vec()]);
+            assert!(dead_letter_stream_length(&factory, &dead_letter_segment).await > 0);
+        });
+    }
+
+    // create the data of a segment holding the events from offset 0.
+    fn create_segment_data(segment: &ScopedSegment, events: &[&[u8]]) -> SegmentDataBuffer {
+        let mut value = BytesMut::new();
+        for event in events {
+            value.put_i32(EventCommand::TYPE_CODE);
+            value.put_i32(event.len() as i32);
+            value.put_slice(event);
+        }
+        SegmentDataBuffer {
+            segment: segment.to_string(),
+            offset_in_segment: 0,
+            value,
+        }
+    }
+
+    fn create_event(offset: i64, value: Vec<u8>) -> Event {
+        let segment = ScopedSegment::from("scope/stream/0.#epoch.0");
+        let length = value.len() as i32 + 8;
+        Event {
+            offset_in_segment: offset,
+            value,
+            event_pointer: EventPointerVersioned::V1(EventPointerV1::new(segment, offset, length)),
+            ack_handle: None,
+        }
+    }
+
+    async fn dead_letter_stream_length(factory: &ClientFactory, segment: &ScopedSegment) -> i64 {
+        factory
+            .create_segment_metadata_client(segment.clone())
+            .await
+            .fetch_current_segment_length()
+            .await
+            .expect("fetch segment length")
+    }
+}
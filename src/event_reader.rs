@@ -8,27 +8,52 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 //
 
+use crate::ack_tracker::AckTracker;
 use crate::client_factory::ClientFactory;
-use crate::reader_group::reader_group_state::Offset;
-
+use crate::metric::ClientMetrics;
+use crate::reader_group::reader_group_state::{Offset, ReaderGroupStateError};
+use crate::reader_group_config::{ReaderConfig, SegmentAssignmentStrategy, TruncationPolicy};
 use crate::segment_reader::ReaderError;
 use crate::segment_slice::{SegmentDataBuffer, SegmentSlice, SliceMetadata};
 use bytes::BufMut;
-use im::HashMap as ImHashMap;
-use pravega_client_shared::{Reader, ScopedSegment, Segment, SegmentWithRange};
-use std::collections::{HashMap, HashSet};
+use pravega_client_retry::retry_async::retry_async;
+use pravega_client_retry::retry_result::RetryResult;
+use pravega_client_shared::{Reader, ScopedSegment, StreamSegmentsWithPredecessors};
+use snafu::{ResultExt, Snafu};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::sync::oneshot;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::oneshot::error::TryRecvError;
+use tokio::sync::{mpsc, Mutex, Semaphore};
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
 pub type ReaderErrorWithOffset = (ReaderError, i64);
 pub type SegmentReadResult = Result<SegmentDataBuffer, ReaderErrorWithOffset>;
+// the result of a read tagged with the read epoch of the segment at the time the read task was started.
+pub type SegmentReadResultWithEpoch = (u64, SegmentReadResult);
+
+#[derive(Debug, Snafu)]
+pub enum EventReaderError {
+    #[snafu(display(
+        "Segment {} is truncated at offset {} which is behind the current head {}",
+        segment,
+        offset,
+        head
+    ))]
+    SegmentTruncated { segment: String, offset: i64, head: i64 },
+    #[snafu(display("All the streams of the reader group are sealed and have been read completely"))]
+    EndOfStream,
+    #[snafu(display("Failed to update the reader group state: {}", source))]
+    ReaderGroupStateFailure { source: ReaderGroupStateError },
+}
 
-const REBALANCE_INTERVAL: Duration = Duration::from_secs(10);
+// the number of times a reader group state update is retried before the error is returned.
+const READER_GROUP_STATE_RETRIES: i32 = 3;
 
 cfg_if::cfg_if! {
     if #[cfg(test)] {
@@ -70,35 +95,40 @@ cfg_if::cfg_if! {
 ///         stream: Stream::from("stream".to_string()),
 ///     };
 ///     // Create a reader group to read data from the Pravega stream.
-///     let rg = client_factory.create_reader_group(scope, "rg".to_string(), stream).await;
+///     let rg = client_factory
+///         .create_reader_group(scope, "rg".to_string(), stream)
+///         .await
+///         .expect("create reader group");
 ///     // Create a reader under the reader group. The segments of the stream are assigned among the
 ///     // readers which are part of the reader group.
-///     let mut reader1 = rg.create_reader("r1".to_string()).await;
+///     let mut reader1 = rg.create_reader("r1".to_string()).await.expect("create reader");
 ///     // read all events from a given segment slice.
-///     if let Some(mut segment_slice) =  reader1.acquire_segment().await {
+///     if let Some(mut segment_slice) =  reader1.acquire_segment().await.expect("acquire segment") {
 ///         while let Some(event) = segment_slice.next() {
 ///             println!("Event read is {:?}", event);
 ///         }
 ///     }
 ///     // read one event from the a given  segment slice and return it back.
-///     if let Some(mut segment_slice) = reader1.acquire_segment().await {
+///     if let Some(mut segment_slice) = reader1.acquire_segment().await.expect("acquire segment") {
 ///         if let Some(event) = segment_slice.next() {
 ///             println!("Event read is {:?}", event);
 ///             // release the segment slice back to the reader.
-///             reader1.release_segment(segment_slice).await;
+///             reader1.release_segment(segment_slice).await.expect("release segment");
 ///         }
 ///     }
 /// }
 /// ```
 ///
-#[derive(new)]
 pub struct EventReader {
     id: Reader,
     factory: ClientFactory,
-    rx: Receiver<SegmentReadResult>,
-    tx: Sender<SegmentReadResult>,
+    rx: Arc<Mutex<Receiver<SegmentReadResultWithEpoch>>>,
+    tx: Sender<SegmentReadResultWithEpoch>,
     meta: ReaderState,
     rg_state: Arc<Mutex<ReaderGroupState>>,
+    config: ReaderConfig,
+    // true once the reader has been removed from the reader group.
+    closed: bool,
 }
 
 /// Reader meta data.
@@ -109,6 +139,31 @@ pub struct ReaderState {
     slice_stop_reading: HashMap<ScopedSegment, oneshot::Sender<()>>,
     last_segment_release: Instant,
     last_segment_acquire: Instant,
+    generation: u64,
+    truncated_bytes_skipped: u64,
+    ack_tracker: Option<Arc<AckTracker>>,
+    // sealed segments which are completed once all the events read from them are acknowledged, or
+    // once the reader group state can be updated.
+    sealed_pending_ack: HashMap<ScopedSegment, (ReaderError, i64)>,
+    // data received for segments whose slices are out for consumption by an EventReaderHandle.
+    // The buffered data holds the prefetch permits taken by the read tasks.
+    buffered_reads: HashMap<ScopedSegment, VecDeque<SegmentReadResult>>,
+    // limits the bytes prefetched by the read tasks which are not yet consumed by the reader.
+    prefetch_limit: Arc<Semaphore>,
+    // segments truncated past the read offset with the truncation policy Fail, mapped to the read
+    // offset and the head of the segment. They stay assigned to the reader.
+    truncated: HashMap<ScopedSegment, (i64, i64)>,
+    // segments whose slices were dropped without being returned to the reader, mapped to the
+    // offset the slices were handed out at. They are released from the reader.
+    unreturned: HashMap<ScopedSegment, i64>,
+    // segments whose read tasks are stopped by the application.
+    paused: HashSet<ScopedSegment>,
+    // paused segments which start reading again once their slices are returned.
+    resuming: HashSet<ScopedSegment>,
+    // the read epoch of the segments, the reads of a segment issued by its earlier read tasks are
+    // dropped once they are received. Segments without an entry are in the read epoch 0.
+    read_epochs: HashMap<ScopedSegment, u64>,
+    last_read_epoch: u64,
 }
 
 impl ReaderState {
@@ -136,7 +191,8 @@ impl ReaderState {
                 }
                 Err(e) => {
                     error!("Error Segment slice was not returned {:?}", e);
-                    panic!("A Segment slice was not returned to the Reader.");
+                    self.slice_not_returned(segment);
+                    None
                 }
             }
         } else {
@@ -148,6 +204,22 @@ impl ReaderState {
         }
     }
 
+    //
+    // Stop reading a segment whose slice was dropped without being returned, the segment is
+    // released from the reader at the offset the slice was handed out at.
+    //
+    fn slice_not_returned(&mut self, segment: &ScopedSegment) {
+        if let Some(offset) = self.slices_dished_out.remove(segment) {
+            warn!(
+                "segment slice {:?} was not returned, releasing the segment at offset {}",
+                segment, offset
+            );
+            self.stop_reading(segment);
+            self.discard_buffered_reads(segment);
+            self.unreturned.insert(segment.clone(), offset);
+        }
+    }
+
     fn close_all_slice_return_channel(&mut self) {
         for (_, mut rx) in self.slice_release_receiver.drain() {
             rx.close();
@@ -201,13 +273,15 @@ impl ReaderState {
     }
 
     //
-    // Use the stored oneshot::Sender to stop segment reading background task.
+    // Use the stored oneshot::Sender to stop segment reading background task. The reads which are
+    // in flight are dropped once they are received.
     //
     fn stop_reading(&mut self, segment: &ScopedSegment) {
         if let Some(tx) = self.slice_stop_reading.remove(segment) {
             if tx.send(()).is_err() {
                 debug!("Channel already closed, ignoring the error");
             }
+            self.new_read_epoch(segment);
         }
     }
 
@@ -215,13 +289,196 @@ impl ReaderState {
     // Stop all the background tasks that are trying to read from owned segments.
     //
     fn stop_reading_all(&mut self) {
-        for (_, tx) in self.slice_stop_reading.drain() {
-            if tx.send(()).is_err() {
-                debug!("Channel already closed, ignoring the error");
+        let segments: Vec<ScopedSegment> = self.slice_stop_reading.keys().cloned().collect();
+        for segment in segments {
+            self.stop_reading(&segment);
+        }
+    }
+
+    //
+    // Start a new read epoch for the segment, the reads of the earlier epochs are stale.
+    //
+    fn new_read_epoch(&mut self, segment: &ScopedSegment) {
+        self.last_read_epoch += 1;
+        self.read_epochs.insert(segment.clone(), self.last_read_epoch);
+    }
+
+    //
+    // Return the read epoch of the segment, the read task of the segment tags its reads with it.
+    //
+    fn read_epoch(&self, segment: &ScopedSegment) -> u64 {
+        self.read_epochs.get(segment).copied().unwrap_or(0)
+    }
+
+    //
+    // Return true if the read was issued by the current read task of its segment.
+    //
+    fn is_current_read(&self, (epoch, read_result): &SegmentReadResultWithEpoch) -> bool {
+        let segment = match read_result {
+            Ok(data) => ScopedSegment::from(data.segment.as_str()),
+            Err((e, _)) => ScopedSegment::from(e.get_segment().as_str()),
+        };
+        self.read_epoch(&segment) == *epoch
+    }
+
+    //
+    // Drop all the segments owned by the reader. This is used when the reader group is reset and
+    // the segments are no longer assigned to this reader.
+    //
+    fn drop_all_segments(&mut self) {
+        self.stop_reading_all();
+        self.close_all_slice_return_channel();
+        self.slices.clear();
+        self.slices_dished_out.clear();
+        self.sealed_pending_ack.clear();
+        self.truncated.clear();
+        self.discard_all_buffered_reads();
+        self.unreturned.clear();
+        self.paused.clear();
+        self.resuming.clear();
+        if let Some(tracker) = &self.ack_tracker {
+            tracker.forget_all();
+        }
+    }
+
+    //
+    // Drop a segment owned by the reader which is no longer assigned to this reader, e.g. since
+    // its stream has been removed from the reader group. A slice of the segment which is out for
+    // consumption is ignored once it is returned.
+    //
+    fn drop_segment(&mut self, segment: &ScopedSegment) {
+        self.stop_reading(segment);
+        if let Some(mut rx) = self.slice_release_receiver.remove(segment) {
+            rx.close();
+        }
+        self.slices.remove(segment);
+        self.slices_dished_out.remove(segment);
+        self.sealed_pending_ack.remove(segment);
+        self.truncated.remove(segment);
+        self.discard_buffered_reads(segment);
+        self.unreturned.remove(segment);
+        self.paused.remove(segment);
+        self.resuming.remove(segment);
+        if let Some(tracker) = &self.ack_tracker {
+            tracker.forget(segment);
+        }
+    }
+
+    //
+    // Take back the segment slices which have been returned without waiting for the others.
+    //
+    fn collect_returned_slices(&mut self) {
+        let dished_out: Vec<ScopedSegment> = self.slices_dished_out.keys().cloned().collect();
+        for segment in dished_out {
+            let returned = match self.slice_release_receiver.get_mut(&segment) {
+                Some(receiver) => match receiver.try_recv() {
+                    Ok(returned_meta) => returned_meta,
+                    Err(TryRecvError::Empty) => continue,
+                    Err(TryRecvError::Closed) => {
+                        error!("Error Segment slice {:?} was not returned", segment);
+                        self.slice_release_receiver.remove(&segment);
+                        self.slice_not_returned(&segment);
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            debug!("SegmentSlice returned {:?}", returned);
+            self.slice_release_receiver.remove(&segment);
+            self.slices_dished_out.remove(&segment);
+            match returned {
+                Some(meta) => self.add_slices(meta),
+                // the segment has been released from the reader.
+                None => self.discard_buffered_reads(&segment),
+            }
+        }
+    }
+
+    //
+    // Keep the data received for a segment which is out for consumption.
+    //
+    fn buffer_read(&mut self, segment: ScopedSegment, read_result: SegmentReadResult) {
+        self.buffered_reads
+            .entry(segment)
+            .or_default()
+            .push_back(read_result);
+    }
+
+    //
+    // Drop the data received for a segment and give back the prefetch permits it holds.
+    //
+    fn discard_buffered_reads(&mut self, segment: &ScopedSegment) {
+        if let Some(reads) = self.buffered_reads.remove(segment) {
+            for data in reads.into_iter().flatten() {
+                self.prefetch_limit.add_permits(data.value.len());
             }
         }
     }
 
+    //
+    // Drop the data received for all the segments.
+    //
+    fn discard_all_buffered_reads(&mut self) {
+        let segments: Vec<ScopedSegment> = self.buffered_reads.keys().cloned().collect();
+        for segment in segments {
+            self.discard_buffered_reads(&segment);
+        }
+    }
+
+    //
+    // Take the oldest data received for a segment which is no longer out for consumption.
+    //
+    fn take_buffered_read(&mut self) -> Option<SegmentReadResult> {
+        let segment = self
+            .buffered_reads
+            .keys()
+            .find(|segment| !self.slices_dished_out.contains_key(segment))?
+            .clone();
+        let reads = self.buffered_reads.get_mut(&segment)?;
+        let read_result = reads.pop_front();
+        if reads.is_empty() {
+            self.buffered_reads.remove(&segment);
+        }
+        read_result
+    }
+
+    //
+    // Return the offset of the segment which can be published to the reader group state. If ack
+    // tracking is enabled this is the offset of the lowest event which has not been acknowledged.
+    //
+    fn committed_offset(&self, segment: &ScopedSegment, read_offset: i64) -> i64 {
+        self.ack_tracker.as_ref().map_or(read_offset, |tracker| {
+            tracker.committed_offset(segment, read_offset)
+        })
+    }
+
+    //
+    // Return the offsets of all the segments owned by the reader which can be published to the
+    // reader group state.
+    //
+    fn committed_offsets(&self) -> HashMap<ScopedSegment, Offset> {
+        let mut offset_map: HashMap<ScopedSegment, Offset> = HashMap::new();
+        for (seg, off) in &self.slices_dished_out {
+            offset_map.insert(seg.clone(), Offset::new(self.committed_offset(seg, *off)));
+        }
+        for (seg, meta) in &self.slices {
+            offset_map.insert(
+                seg.clone(),
+                Offset::new(self.committed_offset(seg, meta.read_offset)),
+            );
+        }
+        for (seg, (_, off)) in &self.sealed_pending_ack {
+            offset_map.insert(seg.clone(), Offset::new(self.committed_offset(seg, *off)));
+        }
+        for (seg, (off, _)) in &self.truncated {
+            offset_map.insert(seg.clone(), Offset::new(self.committed_offset(seg, *off)));
+        }
+        for (seg, off) in &self.unreturned {
+            offset_map.insert(seg.clone(), Offset::new(self.committed_offset(seg, *off)));
+        }
+        offset_map
+    }
+
     fn get_segment_id_with_data(&self) -> Option<ScopedSegment> {
         self.slices
             .iter()
@@ -232,28 +489,36 @@ impl ReaderState {
 impl EventReader {
     ///
     /// Initialize the reader. This fetches the assigned segments from the TableSynchronizer and
-    /// spawns background tasks to start reads from those Segments.
+    /// spawns background tasks to start reads from those Segments. The reader group state is
+    /// read a few times before the error is returned.
     ///
     pub async fn init_reader(
         id: String,
         rg_state: Arc<Mutex<ReaderGroupState>>,
         factory: ClientFactory,
-    ) -> Self {
+        config: ReaderConfig,
+    ) -> Result<Self, ReaderGroupStateError> {
         let reader = Reader::from(id);
-        let new_segments_to_acquire = rg_state
-            .lock()
-            .await
-            .compute_segments_to_acquire_or_release(&reader)
-            .await;
-        // attempt acquiring the desired number of segments.
-        if new_segments_to_acquire > 0 {
-            for _ in 0..new_segments_to_acquire {
-                if let Some(seg) = rg_state
+        let generation = retry_rg_state(&factory, &reader, "fetch generation", || async {
+            rg_state.lock().await.get_generation().await
+        })
+        .await?;
+        let new_segments_to_acquire =
+            retry_rg_state(&factory, &reader, "compute segments to acquire", || async {
+                rg_state
                     .lock()
                     .await
-                    .assign_segment_to_reader(&reader)
+                    .compute_segments_to_acquire_or_release(&reader)
                     .await
-                    .expect("Error while waiting for segments to be assigned")
+            })
+            .await?;
+        // attempt acquiring the desired number of segments.
+        if new_segments_to_acquire > 0 {
+            for _ in 0..new_segments_to_acquire {
+                if let Some(seg) = retry_rg_state(&factory, &reader, "assign segment", || async {
+                    rg_state.lock().await.assign_segment_to_reader(&reader).await
+                })
+                .await?
                 {
                     debug!("Acquiring segment {:?} for reader {:?}", seg, reader);
                 } else {
@@ -263,12 +528,14 @@ impl EventReader {
             }
         }
         // Get all assigned segments for the reader.
-        let mut assigned_segments = rg_state
-            .lock()
-            .await
-            .get_segments_for_reader(&reader)
-            .await
-            .expect("Error while fetching currently assigned segments");
+        let mut assigned_segments = retry_rg_state(&factory, &reader, "read segments", || async {
+            rg_state.lock().await.get_segments_for_reader(&reader).await
+        })
+        .await
+        .map_err(|source| ReaderGroupStateError::SyncError {
+            error_msg: format!("read segments of reader {:?}", reader),
+            source,
+        })?;
 
         let mut slice_meta_map: HashMap<ScopedSegment, SliceMetadata> = HashMap::new();
         slice_meta_map.extend(assigned_segments.drain().map(|(seg, offset)| {
@@ -284,6 +551,7 @@ impl EventReader {
         }));
 
         let (tx, rx) = mpsc::channel(1);
+        let prefetch_limit = Arc::new(Semaphore::new(config.get_max_prefetch_bytes()));
         let mut stop_reading_map: HashMap<ScopedSegment, oneshot::Sender<()>> = HashMap::new();
         // spawn background fetch tasks.
         slice_meta_map.iter().for_each(|(segment, meta)| {
@@ -293,14 +561,17 @@ impl EventReader {
             tokio::spawn(SegmentSlice::get_segment_data(
                 segment.clone(),
                 meta.start_offset,
+                0,
                 tx.clone(),
                 rx_stop,
                 factory.clone(),
+                config.get_read_buffer_size(),
+                prefetch_limit.clone(),
             ));
         });
 
         // initialize the event reader.
-        EventReader::init_event_reader(
+        let mut event_reader = EventReader::init_event_reader(
             rg_state,
             reader,
             factory,
@@ -308,7 +579,14 @@ impl EventReader {
             rx,
             slice_meta_map,
             stop_reading_map,
-        )
+        );
+        event_reader.meta.generation = generation;
+        if config.is_ack_tracking_enabled() {
+            event_reader.meta.ack_tracker = Some(Arc::new(AckTracker::default()));
+        }
+        event_reader.config = config;
+        event_reader.meta.prefetch_limit = prefetch_limit;
+        Ok(event_reader)
     }
 
     #[doc(hidden)]
@@ -316,15 +594,15 @@ impl EventReader {
         rg_state: Arc<Mutex<ReaderGroupState>>,
         id: Reader,
         factory: ClientFactory,
-        tx: Sender<SegmentReadResult>,
-        rx: Receiver<SegmentReadResult>,
+        tx: Sender<SegmentReadResultWithEpoch>,
+        rx: Receiver<SegmentReadResultWithEpoch>,
         segment_slice_map: HashMap<ScopedSegment, SliceMetadata>,
         slice_stop_reading: HashMap<ScopedSegment, oneshot::Sender<()>>,
     ) -> Self {
         EventReader {
             id,
             factory,
-            rx,
+            rx: Arc::new(Mutex::new(rx)),
             tx,
             meta: ReaderState {
                 slices: segment_slice_map,
@@ -333,8 +611,22 @@ impl EventReader {
                 slice_stop_reading,
                 last_segment_release: Instant::now(),
                 last_segment_acquire: Instant::now(),
+                generation: 0,
+                truncated_bytes_skipped: 0,
+                ack_tracker: None,
+                sealed_pending_ack: HashMap::new(),
+                truncated: HashMap::new(),
+                unreturned: HashMap::new(),
+                buffered_reads: HashMap::new(),
+                prefetch_limit: Arc::new(Semaphore::new(ReaderConfig::default().get_max_prefetch_bytes())),
+                paused: HashSet::new(),
+                resuming: HashSet::new(),
+                read_epochs: HashMap::new(),
+                last_read_epoch: 0,
             },
             rg_state,
+            config: ReaderConfig::default(),
+            closed: false,
         }
     }
 
@@ -345,23 +637,51 @@ impl EventReader {
         self.meta.last_segment_acquire = time;
     }
 
+    // for testing purposes, the read epoch of the data sent to the reader.
+    #[cfg(test)]
+    pub(crate) fn get_read_epoch(&self, segment: &ScopedSegment) -> u64 {
+        self.meta.read_epoch(segment)
+    }
+
     ///
     /// Release a partially read segment slice back to event reader.
+    /// If the segment is handed over to another reader while rebalancing and the reader group
+    /// state cannot be updated after a few retries, the error is returned and the segment stays
+    /// assigned to this reader.
     ///
-    pub async fn release_segment(&mut self, mut slice: SegmentSlice) {
+    pub async fn release_segment(&mut self, slice: SegmentSlice) -> Result<(), ReaderGroupStateError> {
         info!(
             "releasing segment slice {} from reader {}",
             slice.meta.scoped_segment, self.id
         );
+        self.release_returned_slice(slice).await
+    }
+
+    // Return the slice to the reader and release its segment from the reader if needed.
+    async fn release_returned_slice(&mut self, mut slice: SegmentSlice) -> Result<(), ReaderGroupStateError> {
         //update meta data.
         let scoped_segment = ScopedSegment::from(slice.meta.scoped_segment.clone().as_str());
+        if !self.meta.slices_dished_out.contains_key(&scoped_segment) {
+            info!(
+                "segment {} is no longer owned by reader {}, ignoring the release",
+                scoped_segment, self.id
+            );
+            return Ok(());
+        }
+        let rebalance = self.meta.last_segment_release.elapsed() > self.config.get_rebalance_interval();
+        // Drop the segments owned by this reader if the reader group has been reset, the returned
+        // slice is ignored.
+        if rebalance && self.check_reader_group_reset().await? {
+            return Ok(());
+        }
         self.meta.add_slices(slice.meta.clone());
         self.meta.slices_dished_out.remove(&scoped_segment);
 
-        if self.meta.last_segment_release.elapsed() > REBALANCE_INTERVAL {
+        if rebalance {
             debug!("try to rebalance segments across readers");
+            self.send_heartbeat().await?;
             let read_offset = slice.meta.read_offset;
-            self.release_segment_from_reader(slice, read_offset).await;
+            self.release_segment_from_reader(slice, read_offset).await?;
             self.meta.last_segment_release = Instant::now();
         } else {
             //send an indication to the waiting rx that slice has been returned.
@@ -373,15 +693,20 @@ impl EventReader {
                     );
                 }
             } else {
-                panic!("This is unexpected, No sender for SegmentSlice present.");
+                warn!("No sender present for segment slice {:?}", slice.meta);
             }
         }
+        Ok(())
     }
 
     ///
     /// Release a segment back to the reader and also indicate the offset upto which the segment slice is consumed.
     ///
-    pub async fn release_segment_at(&mut self, slice: SegmentSlice, offset: i64) {
+    pub async fn release_segment_at(
+        &mut self,
+        slice: SegmentSlice,
+        offset: i64,
+    ) -> Result<(), ReaderGroupStateError> {
         info!(
             "releasing segment slice {} at offset {}",
             slice.meta.scoped_segment, offset
@@ -399,316 +724,950 @@ impl EventReader {
             "the offset where the segment slice is released should be less than the end offset"
         );
         let segment = ScopedSegment::from(slice.meta.scoped_segment.as_str());
+        if !self.meta.slices_dished_out.contains_key(&segment) {
+            info!(
+                "segment {} is no longer owned by reader {}, ignoring the release",
+                segment, self.id
+            );
+            return Ok(());
+        }
         if slice.meta.read_offset != offset {
             self.meta.stop_reading(&segment);
+            self.meta.discard_buffered_reads(&segment);
+            // the events after the offset are delivered again.
+            if let Some(tracker) = &self.meta.ack_tracker {
+                tracker.forget_from(&segment, offset);
+            }
 
+            // the slice is replayed from the offset, it may be released at the same offset again.
             let slice_meta = SliceMetadata {
-                start_offset: slice.meta.read_offset,
+                start_offset: offset,
                 scoped_segment: slice.meta.scoped_segment.clone(),
                 last_event_offset: slice.meta.last_event_offset,
                 read_offset: offset,
                 end_offset: slice.meta.end_offset,
                 segment_data: SegmentDataBuffer::empty(),
                 partial_data_present: false,
+                parsed_segment: slice.meta.parsed_segment.clone(),
             };
 
-            // reinitialize the segment data reactor.
-            let (tx_drop_fetch, rx_drop_fetch) = oneshot::channel();
-            self.factory.get_runtime().spawn(SegmentSlice::get_segment_data(
-                segment.clone(),
-                slice_meta.read_offset, // start reading from the offset provided.
-                self.tx.clone(),
-                rx_drop_fetch,
-                self.factory.clone(),
-            ));
-            self.meta.add_stop_reading_tx(segment.clone(), tx_drop_fetch);
+            // reinitialize the segment data reactor, a paused segment starts reading once it is resumed.
+            if !self.meta.paused.contains(&segment) {
+                self.spawn_read_task(segment.clone(), slice_meta.read_offset);
+            }
             self.meta.add_slices(slice_meta);
             self.meta.slices_dished_out.remove(&segment);
+            Ok(())
         } else {
-            self.release_segment(slice).await;
+            self.release_returned_slice(slice).await
         }
     }
 
     ///
     /// Mark the reader as offline. This will ensure the segments owned by this reader is distributed
-    /// to other readers in the ReaderGroup.
+    /// to other readers in the ReaderGroup. A failure to update the ReaderGroup is logged, use
+    /// [`EventReader::close`] to handle it.
     ///
     pub async fn reader_offline(&mut self) {
+        if let Err(e) = self.close().await {
+            error!("failed to put reader {} offline: {}", self.id, e);
+        }
+    }
+
+    ///
+    /// Mark the reader as offline and remove it from the ReaderGroup. The segments owned by this
+    /// reader are distributed to other readers in the ReaderGroup.
+    /// If ack tracking is enabled the segments are handed over at the offset of their lowest
+    /// un-acknowledged event. If the ReaderGroup cannot be updated the error is returned and the
+    /// reader keeps its segments, closing a reader which is already closed has no effect.
+    ///
+    /// A reader which is dropped without being closed is put offline by a background task.
+    ///
+    pub async fn close(&mut self) -> Result<(), ReaderGroupStateError> {
+        if self.closed {
+            return Ok(());
+        }
         info!("putting reader {} offline", self.id);
+        let offset_map = self.meta.committed_offsets();
+        self.rg_state
+            .lock()
+            .await
+            .remove_reader(&self.id, offset_map)
+            .await?;
+        self.closed = true;
         // stop reading from all the segments.
         self.meta.stop_reading_all();
         // Close all slice return Receivers.
         self.meta.close_all_slice_return_channel();
-        // use the updated map to return the data.
-
-        let mut offset_map: HashMap<ScopedSegment, Offset> = HashMap::new();
-        for (seg, off) in self.meta.slices_dished_out.drain() {
-            offset_map.insert(seg, Offset::new(off));
-        }
-        for (_, meta) in self.meta.slices.drain() {
-            offset_map.insert(
-                ScopedSegment::from(meta.scoped_segment.as_str()),
-                Offset::new(meta.read_offset),
-            );
+        self.meta.slices_dished_out.clear();
+        self.meta.slices.clear();
+        self.meta.sealed_pending_ack.clear();
+        self.meta.truncated.clear();
+        self.meta.discard_all_buffered_reads();
+        self.meta.unreturned.clear();
+        self.meta.paused.clear();
+        self.meta.resuming.clear();
+        if let Some(tracker) = &self.meta.ack_tracker {
+            tracker.forget_all();
         }
+        Ok(())
+    }
+
+    ///
+    /// Publish the read offsets of the segments owned by this reader to the reader group state.
+    /// This ensures the stream cuts generated by the reader group reflect the events read so far.
+    /// A segment slice which is out for consumption is published at the offset it was acquired at.
+    /// If ack tracking is enabled no offset past the lowest un-acknowledged event is published.
+    ///
+    pub async fn publish_positions(&mut self) -> Result<(), ReaderGroupStateError> {
+        let offset_map = self.meta.committed_offsets();
+        debug!("publishing positions {:?} of reader {}", offset_map, self.id);
         self.rg_state
             .lock()
             .await
-            .remove_reader(&self.id, offset_map)
+            .update_reader_positions(&self.id, offset_map)
             .await
-            .expect("Update ReaderGroup to ensure reader is offline");
+    }
+
+    ///
+    /// Pause reading from a segment owned by this reader. The background task prefetching data
+    /// from the segment is stopped while the segment stays assigned to this reader, the data which
+    /// has already been received can still be acquired. Returns false if the segment is not owned
+    /// by this reader.
+    ///
+    pub fn pause(&mut self, segment: &ScopedSegment) -> bool {
+        if !self.meta.slices.contains_key(segment) && !self.meta.slices_dished_out.contains_key(segment) {
+            info!(
+                "segment {} is not owned by reader {}, ignoring the pause",
+                segment, self.id
+            );
+            return false;
+        }
+        info!("pausing segment {} of reader {}", segment, self.id);
+        self.meta.stop_reading(segment);
+        // the data received while the slice is out for consumption is read again once resumed.
+        self.meta.discard_buffered_reads(segment);
+        self.meta.resuming.remove(segment);
+        self.meta.paused.insert(segment.clone());
+        true
+    }
+
+    ///
+    /// Resume reading from a paused segment. Reading continues from the offset up to which data
+    /// has been received before the segment was paused. A segment whose slice is out for
+    /// consumption resumes once the slice is returned. Returns false if the segment is not paused.
+    ///
+    pub fn resume(&mut self, segment: &ScopedSegment) -> bool {
+        if !self.meta.paused.remove(segment) {
+            info!(
+                "segment {} of reader {} is not paused, ignoring the resume",
+                segment, self.id
+            );
+            return false;
+        }
+        info!("resuming segment {} of reader {}", segment, self.id);
+        self.meta.resuming.insert(segment.clone());
+        self.restart_resumed_segments();
+        true
+    }
+
+    ///
+    /// Convert the reader into a handle which can be shared by several tasks.
+    ///
+    pub fn into_handle(self) -> EventReaderHandle {
+        let rx = self.rx.clone();
+        EventReaderHandle {
+            reader: Arc::new(Mutex::new(self)),
+            rx,
+        }
     }
 
     // Release the segment of the provided SegmentSlice from the reader. This segment is marked as
-    // unassigned in the reader group state and other reads can acquire it.
-    async fn release_segment_from_reader(&mut self, mut slice: SegmentSlice, read_offset: i64) {
+    // unassigned in the reader group state and other reads can acquire it. The segment is kept by
+    // the reader if the reader group state cannot be updated.
+    async fn release_segment_from_reader(
+        &mut self,
+        mut slice: SegmentSlice,
+        read_offset: i64,
+    ) -> Result<(), ReaderGroupStateError> {
         let new_segments_to_release = self
-            .rg_state
-            .lock()
-            .await
-            .compute_segments_to_acquire_or_release(&self.id)
-            .await;
-        let segment = ScopedSegment::from(slice.meta.scoped_segment.as_str());
+            .retry_rg_state("compute segments to release", || async {
+                self.rg_state
+                    .lock()
+                    .await
+                    .compute_segments_to_acquire_or_release(&self.id)
+                    .await
+            })
+            .await?;
         // check if segments needs to be released from the reader
-        if new_segments_to_release < 0 {
-            // Stop reading from the segment.
-            self.meta.stop_reading(&segment);
-            self.meta
-                .slices
-                .remove(&segment)
-                .expect("Segment missing in meta while releasing from reader");
-            // Send None to the waiting slice_return_rx.
-            if let Some(tx) = slice.slice_return_tx.take() {
-                if let Err(_e) = tx.send(None) {
-                    warn!(
-                        "Failed to send segment slice release data for slice {:?}",
-                        slice.meta
-                    );
-                }
-            } else {
-                panic!("This is unexpected, No sender for SegmentSlice present.");
-            }
+        if new_segments_to_release >= 0 {
+            return Ok(());
+        }
+        let segment = ScopedSegment::from(slice.meta.scoped_segment.as_str());
+        let offset = Offset::new(self.meta.committed_offset(&segment, read_offset));
+        self.retry_rg_state("release segment", || async {
             self.rg_state
                 .lock()
                 .await
-                .release_segment(&self.id, &segment, &Offset::new(read_offset))
+                .release_segment(&self.id, &segment, &offset)
                 .await
-                .expect("Failed to release segment from RG state for reader");
+        })
+        .await?;
+        // Stop reading from the segment.
+        self.meta.stop_reading(&segment);
+        self.meta.discard_buffered_reads(&segment);
+        self.meta.paused.remove(&segment);
+        self.meta.resuming.remove(&segment);
+        self.meta.slices.remove(&segment);
+        // Send None to the waiting slice_return_rx.
+        if let Some(tx) = slice.slice_return_tx.take() {
+            if let Err(_e) = tx.send(None) {
+                warn!(
+                    "Failed to send segment slice release data for slice {:?}",
+                    slice.meta
+                );
+            }
+        } else {
+            warn!("No sender present for segment slice {:?}", slice.meta);
+        }
+        if let Some(tracker) = &self.meta.ack_tracker {
+            // the un-acknowledged events are delivered again by the reader acquiring the segment.
+            tracker.forget(&segment);
+        }
+        Ok(())
+    }
+
+    //
+    // Release the segments whose slices were not returned to the reader, a segment which cannot be
+    // released is released again by the next acquire.
+    //
+    async fn release_unreturned_segments(&mut self) -> Result<(), ReaderGroupStateError> {
+        let unreturned: Vec<(ScopedSegment, i64)> = self
+            .meta
+            .unreturned
+            .iter()
+            .map(|(segment, offset)| (segment.clone(), *offset))
+            .collect();
+        for (segment, read_offset) in unreturned {
+            let offset = Offset::new(self.meta.committed_offset(&segment, read_offset));
+            self.retry_rg_state("release segment", || async {
+                self.rg_state
+                    .lock()
+                    .await
+                    .release_segment(&self.id, &segment, &offset)
+                    .await
+            })
+            .await?;
+            info!(
+                "segment {} whose slice was not returned is released from reader {} at offset {:?}",
+                segment, self.id, offset
+            );
+            self.meta.unreturned.remove(&segment);
+            if let Some(tracker) = &self.meta.ack_tracker {
+                // the un-acknowledged events are delivered again by the reader acquiring the segment.
+                tracker.forget(&segment);
+            }
         }
+        Ok(())
+    }
+
+    //
+    // Perform an update of the reader group state, the update is retried a few times before the
+    // error is returned.
+    //
+    async fn retry_rg_state<T, E, F, Fut>(&self, operation_name: &str, operation: F) -> Result<T, E>
+    where
+        E: Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        retry_rg_state(&self.factory, &self.id, operation_name, operation).await
     }
 
     ///
     /// This function returns a SegmentSlice from the data received from the SegmentStore(s).
     /// Individual events can be read from the data received using `SegmentSlice.next()`.
+    /// `EventReaderError::EndOfStream` is returned once all the streams of the reader group are
+    /// sealed and every segment has been read, while `None` indicates no data is available yet.
     ///
     /// Invoking this function multiple times ensure multiple SegmentSlices corresponding
     /// to different Segments of the stream are received. In-case we receive data for an already
     /// acquired SegmentSlice this method waits until SegmentSlice is completely consumed before
-    /// returning the data.
+    /// returning the data. Use an [`EventReaderHandle`] to consume several SegmentSlices concurrently.
     ///
-    pub async fn acquire_segment(&mut self) -> Option<SegmentSlice> {
+    /// If no segment has data, this function returns as soon as any segment produces data and
+    /// `None` is returned once the acquire timeout of the reader expires.
+    ///
+    pub async fn acquire_segment(&mut self) -> Result<Option<SegmentSlice>, EventReaderError> {
         info!("acquiring segment for reader {}", self.id);
+        if let Some(slice) = self.acquire_ready_segment().await? {
+            return Ok(Some(slice));
+        }
+        let deadline = Instant::now() + self.config.get_acquire_timeout();
+        let rx = self.rx.clone();
+        let mut rx = rx.lock().await;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match timeout(remaining, rx.recv()).await {
+                Ok(Some(read_result)) => {
+                    let read_result = match self.take_current_read(read_result) {
+                        Some(read_result) => read_result,
+                        None => continue,
+                    };
+                    // keep waiting if the data is ignored, e.g. the segment has been released.
+                    let is_data = read_result.is_ok();
+                    let slice = self.process_read_result(read_result).await?;
+                    if slice.is_some() || !is_data {
+                        return Ok(slice);
+                    }
+                }
+                Ok(None) => {
+                    warn!("error getting updates from segment slice for reader {}", self.id);
+                    return Ok(None);
+                }
+                Err(_) => {
+                    info!(
+                        "reader {} owns {} slices but none is ready to read",
+                        self.id,
+                        self.meta.slices.len()
+                    );
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    //
+    // Rebalance the segments if needed and return a SegmentSlice for a segment which already has
+    // event data, this does not wait for data from the SegmentStore(s).
+    //
+    async fn acquire_ready_segment(&mut self) -> Result<Option<SegmentSlice>, EventReaderError> {
+        self.meta.collect_returned_slices();
+        self.release_unreturned_segments()
+            .await
+            .context(ReaderGroupStateFailure {})?;
         // Check if newer segments should be acquired.
-        if self.meta.last_segment_acquire.elapsed() > REBALANCE_INTERVAL {
+        if self.meta.last_segment_acquire.elapsed() > self.config.get_rebalance_interval() {
             info!("need to rebalance segments across readers");
+            // Drop the segments owned by this reader if the reader group has been reset.
+            self.check_reader_group_reset()
+                .await
+                .context(ReaderGroupStateFailure {})?;
+            self.send_heartbeat().await.context(ReaderGroupStateFailure {})?;
+            self.drop_unassigned_segments()
+                .await
+                .context(ReaderGroupStateFailure {})?;
+            self.update_distance_to_tail()
+                .await
+                .context(ReaderGroupStateFailure {})?;
             // Assign newer segments to this reader if available.
-            if let Some(new_segments) = self.assign_segments_to_reader().await {
-                // fetch current segments.
-                let current_segments = self
-                    .rg_state
-                    .lock()
-                    .await
-                    .get_segments_for_reader(&self.id)
-                    .await
-                    .expect("Read segments");
-                let new_segments: HashSet<(ScopedSegment, Offset)> = current_segments
-                    .into_iter()
-                    .filter(|(seg, _off)| new_segments.contains(seg))
-                    .collect();
-                debug!("segments which can be read next are {:?}", new_segments);
-                // Initiate segment reads to the newer segments.
-                self.initiate_segment_reads(new_segments);
+            if self
+                .acquire_new_segments()
+                .await
+                .context(ReaderGroupStateFailure {})?
+            {
                 self.meta.last_segment_acquire = Instant::now();
             }
         }
+        self.complete_acknowledged_segments()
+            .await
+            .context(ReaderGroupStateFailure {})?;
+        self.restart_resumed_segments();
+        // A truncated segment keeps failing the reader until the reader group is reset.
+        if let Some((segment, (offset, head))) = self.meta.truncated.iter().next() {
+            return Err(EventReaderError::SegmentTruncated {
+                segment: segment.to_string(),
+                offset: *offset,
+                head: *head,
+            });
+        }
+        // A reader which owns no segments checks if all the segments have been read.
+        if self.meta.slices.is_empty()
+            && self.meta.slices_dished_out.is_empty()
+            && self
+                .retry_rg_state("check end of stream", || async {
+                    self.rg_state.lock().await.is_end_of_stream().await
+                })
+                .await
+                .context(ReaderGroupStateFailure {})?
+        {
+            info!("reader {} has reached the end of stream", self.id);
+            return Err(EventReaderError::EndOfStream);
+        }
         // Check if any of the segments already has event data and return it.
         if let Some(segment_with_data) = self.meta.get_segment_id_with_data() {
             info!("segment {} has data ready to read", segment_with_data);
             let slice_meta = self.meta.slices.remove(&segment_with_data).unwrap();
-            let segment = ScopedSegment::from(slice_meta.scoped_segment.as_str());
-            // Create an one-shot channel to receive SegmentSlice return.
-            let (slice_return_tx, slice_return_rx) = oneshot::channel();
-            self.meta.add_slice_release_receiver(segment, slice_return_rx);
-
-            info!(
-                "segment slice for {:?} is ready for consumption by reader {}",
-                slice_meta.scoped_segment, self.id,
-            );
-            self.meta
-                .slices_dished_out
-                .insert(segment_with_data, slice_meta.read_offset);
-            Some(SegmentSlice {
-                meta: slice_meta,
-                slice_return_tx: Some(slice_return_tx),
-            })
-        } else if let Ok(option) = timeout(Duration::from_millis(1000), self.rx.recv()).await {
-            if let Some(read_result) = option {
-                match read_result {
-                    // received segment data
-                    Ok(data) => {
-                        let segment = ScopedSegment::from(data.segment.clone().as_str());
-                        info!("new data fetched from server for segment {:?}", segment);
-                        if let Some(mut slice_meta) = self.meta.remove_segment(segment.clone()).await {
-                            if data.offset_in_segment
-                                != slice_meta.read_offset + slice_meta.segment_data.value.len() as i64
-                            {
-                                info!("Data from an invalid offset {:?} observed. Expected offset {:?}. Ignoring this data", data.offset_in_segment, slice_meta.read_offset);
-                                None
-                            } else {
-                                // add received data to Segment slice.
-                                EventReader::add_data_to_segment_slice(data, &mut slice_meta);
-
-                                // Create an one-shot channel to receive SegmentSlice return.
-                                let (slice_return_tx, slice_return_rx) = oneshot::channel();
-                                self.meta
-                                    .add_slice_release_receiver(segment.clone(), slice_return_rx);
-                                self.meta
-                                    .slices_dished_out
-                                    .insert(segment.clone(), slice_meta.read_offset);
-
-                                info!(
-                                    "segment slice for {:?} is ready for consumption by reader {}",
-                                    slice_meta.scoped_segment, self.id,
-                                );
-
-                                Some(SegmentSlice {
-                                    meta: slice_meta,
-                                    slice_return_tx: Some(slice_return_tx),
-                                })
-                            }
-                        } else {
-                            //None is sent if the the segment is released from the reader.
-                            debug!("ignore the received data since None was returned");
-                            return None;
-                        }
-                    }
-                    Err((e, offset)) => {
-                        let segment = ScopedSegment::from(e.get_segment().as_str());
-                        debug!(
-                            "Reader Error observed {:?} on segment {:?} at offset {:?} ",
-                            e, segment, offset
-                        );
-                        // Remove the slice from the reader meta and fetch successors.
-                        if let Some(slice_meta) = self.meta.remove_segment(segment.clone()).await {
-                            if slice_meta.read_offset != offset {
-                                info!("Error at an invalid offset {:?} observed. Expected offset {:?}. Ignoring this data", offset, slice_meta.start_offset);
-                                self.meta.add_slices(slice_meta);
-                                self.meta.slices_dished_out.remove(&segment);
-                            } else {
-                                info!("Segment slice {:?} has received error {:?}", slice_meta, e);
-                                self.fetch_successors(e).await;
-                            }
-                        }
-                        debug!("segment Slice meta {:?}", self.meta.slices);
-                        None
-                    }
-                }
-            } else {
-                warn!("error getting updates from segment slice for reader {}", self.id);
-                None
-            }
+            Ok(Some(self.dish_out_slice(slice_meta)))
         } else {
-            info!(
-                "reader {} owns {} slices but none is ready to read",
-                self.id,
-                self.meta.slices.len()
-            );
-            None
+            Ok(None)
         }
     }
 
     //
-    // Fetch successors of the segment where an error was observed.
-    // ensure we stop the read task and spawn read tasks for the successor segments.
+    // Acquire a segment without waiting for the segment slices which are out for consumption,
+    // the data received for those segments is kept until their slices are returned.
     //
-    async fn fetch_successors(&mut self, e: ReaderError) {
-        match e {
-            ReaderError::SegmentSealed {
-                segment,
-                can_retry: _,
-                operation: _,
-                error_msg: _,
+    async fn try_acquire_segment(&mut self) -> Result<Option<SegmentSlice>, EventReaderError> {
+        if let Some(slice) = self.acquire_ready_segment().await? {
+            return Ok(Some(slice));
+        }
+        while let Some(read_result) = self.meta.take_buffered_read() {
+            if let Some(slice) = self.process_read_result(read_result).await? {
+                return Ok(Some(slice));
             }
-            | ReaderError::SegmentIsTruncated {
-                segment,
-                can_retry: _,
-                operation: _,
-                error_msg: _,
-            } => {
-                let completed_scoped_segment = ScopedSegment::from(segment.as_str());
-                self.meta.stop_reading(&completed_scoped_segment); // stop reading segment.
-
-                // Fetch next segments that can be read from.
-                let successors = self
-                    .factory
-                    .get_controller_client()
-                    .get_successors(&completed_scoped_segment)
-                    .await
-                    .expect("Failed to fetch successors of the segment")
-                    .segment_with_predecessors;
-                info!("Segment Completed {:?}", segment);
-                // Update rg_state with the completed segment and its successors.
-                self.rg_state
-                    .lock()
-                    .await
-                    .segment_completed(&self.id, &completed_scoped_segment, &successors)
-                    .await
-                    .expect("Update segment completed");
-                // Assign newer segments to this reader if available.
-                if let Some(new_segments) = self.assign_segments_to_reader().await {
-                    // fetch current segments.
-                    let current_segments = self
-                        .rg_state
-                        .lock()
-                        .await
-                        .get_segments_for_reader(&self.id)
-                        .await
-                        .expect("Read segments");
-                    let new_segments: HashSet<(ScopedSegment, Offset)> = current_segments
+        }
+        Ok(None)
+    }
+
+    //
+    // Handle the data or the error received from the read task of a segment, unless the segment
+    // is out for consumption.
+    //
+    async fn process_read_result_concurrently(
+        &mut self,
+        read_result: SegmentReadResultWithEpoch,
+    ) -> Result<Option<SegmentSlice>, EventReaderError> {
+        self.meta.collect_returned_slices();
+        let read_result = match self.take_current_read(read_result) {
+            Some(read_result) => read_result,
+            None => return Ok(None),
+        };
+        let segment = match &read_result {
+            Ok(data) => ScopedSegment::from(data.segment.as_str()),
+            Err((e, _)) => ScopedSegment::from(e.get_segment().as_str()),
+        };
+        if self.meta.slices_dished_out.contains_key(&segment) {
+            debug!(
+                "segment slice {:?} is out for consumption, keep the data",
+                segment
+            );
+            self.meta.buffer_read(segment, read_result);
+            return Ok(None);
+        }
+        self.process_read_result(read_result).await
+    }
+
+    //
+    // Start reading again from the resumed segments which are not out for consumption.
+    //
+    fn restart_resumed_segments(&mut self) {
+        let segments: Vec<(ScopedSegment, i64)> = self
+            .meta
+            .resuming
+            .iter()
+            .filter_map(|segment| {
+                self.meta.slices.get(segment).map(|meta| {
+                    // the data which has been received is not read again.
+                    let offset = meta.read_offset + meta.segment_data.value.len() as i64;
+                    (segment.clone(), offset)
+                })
+            })
+            .collect();
+        for (segment, offset) in segments {
+            self.meta.resuming.remove(&segment);
+            self.spawn_read_task(segment, offset);
+        }
+    }
+
+    //
+    // Drop the result of a read issued by an earlier read task of its segment, e.g. before the
+    // segment was paused. The data is read again by the current read task of the segment.
+    //
+    fn take_current_read(&self, read_result: SegmentReadResultWithEpoch) -> Option<SegmentReadResult> {
+        if self.meta.is_current_read(&read_result) {
+            return Some(read_result.1);
+        }
+        debug!("dropping the result of a stale read {:?}", read_result);
+        if let Ok(data) = &read_result.1 {
+            self.meta.prefetch_limit.add_permits(data.value.len());
+        }
+        None
+    }
+
+    //
+    // Spawn a background task to read data from the segment starting at the given offset.
+    //
+    fn spawn_read_task(&mut self, segment: ScopedSegment, offset: i64) {
+        let (tx_drop_fetch, rx_drop_fetch) = oneshot::channel();
+        let read_epoch = self.meta.read_epoch(&segment);
+        self.factory.get_runtime().spawn(SegmentSlice::get_segment_data(
+            segment.clone(),
+            offset,
+            read_epoch,
+            self.tx.clone(),
+            rx_drop_fetch,
+            self.factory.clone(),
+            self.config.get_read_buffer_size(),
+            self.meta.prefetch_limit.clone(),
+        ));
+        self.meta.add_stop_reading_tx(segment, tx_drop_fetch);
+    }
+
+    //
+    // Hand out a SegmentSlice for the segment to the application.
+    //
+    fn dish_out_slice(&mut self, slice_meta: SliceMetadata) -> SegmentSlice {
+        let segment = ScopedSegment::from(slice_meta.scoped_segment.as_str());
+        // Create an one-shot channel to receive SegmentSlice return.
+        let (slice_return_tx, slice_return_rx) = oneshot::channel();
+        self.meta
+            .add_slice_release_receiver(segment.clone(), slice_return_rx);
+        info!(
+            "segment slice for {:?} is ready for consumption by reader {}",
+            slice_meta.scoped_segment, self.id,
+        );
+        self.meta
+            .slices_dished_out
+            .insert(segment, slice_meta.read_offset);
+        SegmentSlice {
+            meta: slice_meta,
+            slice_return_tx: Some(slice_return_tx),
+            ack_tracker: self.meta.ack_tracker.clone(),
+        }
+    }
+
+    //
+    // Handle the data or the error received from the read task of a segment.
+    //
+    async fn process_read_result(
+        &mut self,
+        read_result: SegmentReadResult,
+    ) -> Result<Option<SegmentSlice>, EventReaderError> {
+        match read_result {
+            // received segment data
+            Ok(data) => {
+                // the data is no longer prefetched once it is received by the reader.
+                self.meta.prefetch_limit.add_permits(data.value.len());
+                let segment = ScopedSegment::from(data.segment.clone().as_str());
+                info!("new data fetched from server for segment {:?}", segment);
+                if let Some(mut slice_meta) = self.meta.remove_segment(segment.clone()).await {
+                    if data.offset_in_segment
+                        != slice_meta.read_offset + slice_meta.segment_data.value.len() as i64
+                    {
+                        info!("Data from an invalid offset {:?} observed. Expected offset {:?}. Ignoring this data", data.offset_in_segment, slice_meta.read_offset);
+                        self.meta.add_slices(slice_meta);
+                        self.meta.slices_dished_out.remove(&segment);
+                        Ok(None)
+                    } else {
+                        // add received data to Segment slice.
+                        EventReader::add_data_to_segment_slice(data, &mut slice_meta);
+                        Ok(Some(self.dish_out_slice(slice_meta)))
+                    }
+                } else {
+                    //None is sent if the the segment is released from the reader.
+                    debug!("ignore the received data since None was returned");
+                    Ok(None)
+                }
+            }
+            Err((e, offset)) => {
+                let segment = ScopedSegment::from(e.get_segment().as_str());
+                debug!(
+                    "Reader Error observed {:?} on segment {:?} at offset {:?} ",
+                    e, segment, offset
+                );
+                if let ReaderError::SegmentIsTruncated { .. } = e {
+                    self.handle_segment_truncation(segment, offset).await?;
+                    return Ok(None);
+                }
+                // Remove the slice from the reader meta and fetch successors.
+                if let Some(slice_meta) = self.meta.remove_segment(segment.clone()).await {
+                    if slice_meta.read_offset != offset {
+                        info!("Error at an invalid offset {:?} observed. Expected offset {:?}. Ignoring this data", offset, slice_meta.start_offset);
+                        self.meta.add_slices(slice_meta);
+                        self.meta.slices_dished_out.remove(&segment);
+                    } else {
+                        info!("Segment slice {:?} has received error {:?}", slice_meta, e);
+                        self.meta.slices_dished_out.remove(&segment);
+                        let pending_ack = self
+                            .meta
+                            .ack_tracker
+                            .as_ref()
+                            .is_some_and(|tracker| tracker.lowest_pending(&segment).is_some());
+                        if pending_ack {
+                            // the segment is completed once its remaining events are acknowledged.
+                            info!("segment {:?} is sealed but has un-acknowledged events", segment);
+                            self.meta.stop_reading(&segment);
+                            self.meta
+                                .sealed_pending_ack
+                                .insert(segment.clone(), (e, slice_meta.read_offset));
+                        } else {
+                            self.fetch_successors(e, slice_meta.read_offset)
+                                .await
+                                .context(ReaderGroupStateFailure {})?;
+                        }
+                    }
+                }
+                debug!("segment Slice meta {:?}", self.meta.slices);
+                Ok(None)
+            }
+        }
+    }
+
+    ///
+    /// Return the number of bytes this reader skipped because its segments were truncated
+    /// past the offsets it was reading from.
+    ///
+    pub fn get_truncated_bytes_skipped(&self) -> u64 {
+        self.meta.truncated_bytes_skipped
+    }
+
+    //
+    // Handle a segment which was truncated past the offset the reader was reading from. Depending
+    // on the truncation policy of the reader group the reader either skips ahead to the current
+    // head of the segment or stops reading from the segment and returns an error. In the latter
+    // case the segment stays assigned to this reader.
+    //
+    async fn handle_segment_truncation(
+        &mut self,
+        segment: ScopedSegment,
+        offset: i64,
+    ) -> Result<(), EventReaderError> {
+        let slice_meta = match self.meta.remove_segment(segment.clone()).await {
+            Some(meta) => meta,
+            None => return Ok(()),
+        };
+        if offset < slice_meta.read_offset {
+            info!(
+                "Truncation error at an invalid offset {:?} observed. Expected offset {:?}. Ignoring this error",
+                offset, slice_meta.read_offset
+            );
+            self.meta.add_slices(slice_meta);
+            self.meta.slices_dished_out.remove(&segment);
+            return Ok(());
+        }
+        self.meta.stop_reading(&segment);
+        self.meta.slices_dished_out.remove(&segment);
+
+        let head = match self
+            .factory
+            .create_segment_metadata_client(segment.clone())
+            .await
+            .fetch_current_starting_head()
+            .await
+        {
+            Ok(head) => head,
+            Err(e) => {
+                // read the segment again from the same offset, this retries the truncation handling.
+                warn!(
+                    "failed to fetch the head of truncated segment {:?}: {:?}",
+                    segment, e
+                );
+                self.initiate_segment_reads(
+                    vec![(segment, Offset::new(slice_meta.read_offset))]
+                        .into_iter()
+                        .collect(),
+                );
+                return Ok(());
+            }
+        };
+        let policy = match self
+            .retry_rg_state("fetch truncation policy", || async {
+                self.rg_state.lock().await.get_truncation_policy().await
+            })
+            .await
+        {
+            Ok(policy) => policy,
+            Err(e) => {
+                // read the segment again from the same offset, this retries the truncation handling.
+                self.initiate_segment_reads(
+                    vec![(segment, Offset::new(slice_meta.read_offset))]
                         .into_iter()
-                        .filter(|(seg, _off)| new_segments.contains(seg))
-                        .collect();
-                    debug!("Segments which can be read next are {:?}", new_segments);
-                    // Initiate segment reads to the newer segments.
-                    self.initiate_segment_reads(new_segments);
+                        .collect(),
+                );
+                return Err(e).context(ReaderGroupStateFailure {});
+            }
+        };
+        match policy {
+            TruncationPolicy::SkipToHead => {
+                let skipped = (head - slice_meta.read_offset).max(0) as u64;
+                self.meta.truncated_bytes_skipped += skipped;
+                warn!(
+                    "segment {:?} is truncated, reader {} skips {} bytes from offset {} to the head {}",
+                    segment, self.id, skipped, slice_meta.read_offset, head
+                );
+                update!(
+                    ClientMetrics::ReaderTruncatedBytesSkipped,
+                    self.meta.truncated_bytes_skipped,
+                    "Reader" => self.id.to_string()
+                );
+                if let Some(tracker) = &self.meta.ack_tracker {
+                    tracker.forget(&segment);
                 }
+                self.initiate_segment_reads(vec![(segment, Offset::new(head))].into_iter().collect());
+                Ok(())
+            }
+            TruncationPolicy::Fail => {
+                error!(
+                    "segment {:?} is truncated at offset {}, the current head is {}",
+                    segment, slice_meta.read_offset, head
+                );
+                self.meta
+                    .truncated
+                    .insert(segment.clone(), (slice_meta.read_offset, head));
+                Err(EventReaderError::SegmentTruncated {
+                    segment: segment.to_string(),
+                    offset: slice_meta.read_offset,
+                    head,
+                })
+            }
+        }
+    }
+
+    //
+    // Complete the sealed segments whose events have all been acknowledged, this also retries the
+    // sealed segments whose completion could not be recorded in the reader group state.
+    //
+    async fn complete_acknowledged_segments(&mut self) -> Result<(), ReaderGroupStateError> {
+        let tracker = self.meta.ack_tracker.clone();
+        let completed: Vec<ScopedSegment> = self
+            .meta
+            .sealed_pending_ack
+            .keys()
+            .filter(|segment| {
+                tracker
+                    .as_ref()
+                    .is_none_or(|tracker| tracker.lowest_pending(segment).is_none())
+            })
+            .cloned()
+            .collect();
+        for segment in completed {
+            if let Some((e, offset)) = self.meta.sealed_pending_ack.remove(&segment) {
+                self.fetch_successors(e, offset).await?;
+            }
+        }
+        Ok(())
+    }
+
+    //
+    // Fetch successors of the segment where an error was observed.
+    // ensure we stop the read task and spawn read tasks for the successor segments.
+    // If the completed segment cannot be recorded in the reader group state it is kept as a sealed
+    // segment of this reader, so that the next acquire tries again.
+    //
+    async fn fetch_successors(&mut self, e: ReaderError, offset: i64) -> Result<(), ReaderGroupStateError> {
+        let completed_scoped_segment = match &e {
+            ReaderError::SegmentSealed { segment, .. } | ReaderError::SegmentIsTruncated { segment, .. } => {
+                ScopedSegment::from(segment.as_str())
+            }
+            _ => {
+                error!("Error observed while reading from Pravega {:?}", e);
+                return Ok(());
             }
-            _ => error!("Error observed while reading from Pravega {:?}", e),
         };
+        self.meta.stop_reading(&completed_scoped_segment); // stop reading segment.
+
+        // Fetch next segments that can be read from.
+        let successors = match self.get_successors(&completed_scoped_segment).await {
+            Ok(successors) => successors,
+            Err(err) => {
+                self.meta
+                    .sealed_pending_ack
+                    .insert(completed_scoped_segment, (e, offset));
+                return Err(err);
+            }
+        };
+        info!("Segment Completed {:?}", completed_scoped_segment);
+        // Update rg_state with the completed segment and its successors.
+        let result = self
+            .retry_rg_state("complete segment", || async {
+                self.rg_state
+                    .lock()
+                    .await
+                    .segment_completed(&self.id, &completed_scoped_segment, &successors)
+                    .await
+            })
+            .await;
+        if let Err(err) = result {
+            self.meta
+                .sealed_pending_ack
+                .insert(completed_scoped_segment, (e, offset));
+            return Err(err);
+        }
+        // Assign newer segments to this reader if available.
+        self.acquire_new_segments().await?;
+        Ok(())
     }
 
     //
-    // This function tries to acquire newer segments for the reader.
+    // Assign newer segments to this reader if available and start reading from them. Returns
+    // true if the reader was allowed to acquire segments.
     //
-    async fn assign_segments_to_reader(&self) -> Option<Vec<ScopedSegment>> {
-        let mut new_segments: Vec<ScopedSegment> = Vec::new();
-        let new_segments_to_acquire = self
+    async fn acquire_new_segments(&mut self) -> Result<bool, ReaderGroupStateError> {
+        let new_segments = match self.assign_segments_to_reader().await? {
+            Some(new_segments) => new_segments,
+            None => return Ok(false),
+        };
+        // fetch current segments.
+        let current_segments = self
+            .retry_rg_state("read segments", || async {
+                self.rg_state.lock().await.get_segments_for_reader(&self.id).await
+            })
+            .await
+            .map_err(|source| ReaderGroupStateError::SyncError {
+                error_msg: format!("read segments of reader {:?}", self.id),
+                source,
+            })?;
+        let new_segments: HashSet<(ScopedSegment, Offset)> = current_segments
+            .into_iter()
+            .filter(|(seg, _off)| new_segments.contains(seg))
+            .collect();
+        debug!("segments which can be read next are {:?}", new_segments);
+        // Initiate segment reads to the newer segments.
+        self.initiate_segment_reads(new_segments);
+        Ok(true)
+    }
+
+    //
+    // Check if the reader group has been reset since the last check. If so, the segments owned by
+    // this reader have already been returned to the reader group, so they are dropped without
+    // updating the reader group state. This fetches the reader group state, so it is only done when
+    // the reader rebalances its segments.
+    //
+    async fn check_reader_group_reset(&mut self) -> Result<bool, ReaderGroupStateError> {
+        let generation = self
+            .retry_rg_state("fetch generation", || async {
+                self.rg_state.lock().await.get_generation().await
+            })
+            .await?;
+        if generation == self.meta.generation {
+            Ok(false)
+        } else {
+            info!(
+                "reader group has been reset to generation {}, reader {} drops its segments",
+                generation, self.id
+            );
+            self.meta.drop_all_segments();
+            self.meta.generation = generation;
+            Ok(true)
+        }
+    }
+
+    //
+    // Drop the segments owned by this reader which are no longer assigned to it in the reader
+    // group state, e.g. since their stream has been removed from the reader group.
+    //
+    async fn drop_unassigned_segments(&mut self) -> Result<(), ReaderGroupStateError> {
+        let assigned: HashSet<ScopedSegment> = self
+            .retry_rg_state("read segments", || async {
+                self.rg_state.lock().await.get_segments_for_reader(&self.id).await
+            })
+            .await
+            .map_err(|source| ReaderGroupStateError::SyncError {
+                error_msg: format!("read segments of reader {:?}", self.id),
+                source,
+            })?
+            .into_iter()
+            .map(|(segment, _offset)| segment)
+            .collect();
+        for segment in self.meta.committed_offsets().into_keys() {
+            if !assigned.contains(&segment) {
+                info!(
+                    "segment {} is no longer assigned to reader {}, dropping it",
+                    segment, self.id
+                );
+                self.meta.drop_segment(&segment);
+            }
+        }
+        Ok(())
+    }
+
+    //
+    // Let the other readers know this reader is alive, readers that have not sent a heartbeat
+    // within the reader lease are put offline by the reader group state. The segments of a reader
+    // which has been put offline have been given to other readers, so they are dropped and an
+    // error is returned. Other failures are logged and the heartbeat is sent again later.
+    //
+    async fn send_heartbeat(&mut self) -> Result<(), ReaderGroupStateError> {
+        match self.rg_state.lock().await.heartbeat(&self.id).await {
+            Err(e @ ReaderGroupStateError::ReaderOffline { .. }) => {
+                error!("reader {} has been put offline, dropping its segments", self.id);
+                self.meta.drop_all_segments();
+                Err(e)
+            }
+            Err(e) => {
+                warn!("failed to send heartbeat for reader {}: {:?}", self.id, e);
+                Ok(())
+            }
+            Ok(()) => Ok(()),
+        }
+    }
+
+    //
+    // Publish the number of bytes this reader has yet to read from its segments, this is needed
+    // only if segments are assigned based on unread bytes.
+    //
+    async fn update_distance_to_tail(&mut self) -> Result<(), ReaderGroupStateError> {
+        let strategy = self
+            .retry_rg_state("fetch segment assignment strategy", || async {
+                self.rg_state.lock().await.get_segment_assignment_strategy().await
+            })
+            .await?;
+        if strategy != SegmentAssignmentStrategy::UnreadBytes {
+            return Ok(());
+        }
+        let mut offsets: HashMap<ScopedSegment, i64> = self.meta.slices_dished_out.clone();
+        for (seg, meta) in &self.meta.slices {
+            offsets.insert(seg.clone(), meta.read_offset);
+        }
+        let mut distance: u64 = 0;
+        for (seg, offset) in offsets {
+            match self
+                .factory
+                .create_segment_metadata_client(seg.clone())
+                .await
+                .fetch_current_segment_length()
+                .await
+            {
+                Ok(length) if length > offset => distance += (length - offset) as u64,
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("failed to fetch the length of segment {:?}: {:?}", seg, e);
+                    return Ok(());
+                }
+            }
+        }
+        let mut distances = HashMap::new();
+        distances.insert(self.id.clone(), distance);
+        if let Err(e) = self
             .rg_state
             .lock()
             .await
-            .compute_segments_to_acquire_or_release(&self.id)
-            .await;
+            .update_distance_to_tail(distances)
+            .await
+        {
+            warn!("failed to update distance to tail of reader {}: {:?}", self.id, e);
+        }
+        Ok(())
+    }
+
+    //
+    // This function tries to acquire newer segments for the reader.
+    //
+    async fn assign_segments_to_reader(&self) -> Result<Option<Vec<ScopedSegment>>, ReaderGroupStateError> {
+        let mut new_segments: Vec<ScopedSegment> = Vec::new();
+        let new_segments_to_acquire = self
+            .retry_rg_state("compute segments to acquire", || async {
+                self.rg_state
+                    .lock()
+                    .await
+                    .compute_segments_to_acquire_or_release(&self.id)
+                    .await
+            })
+            .await?;
         if new_segments_to_acquire <= 0 {
-            None
+            Ok(None)
         } else {
             for _ in 0..new_segments_to_acquire {
                 if let Some(seg) = self
-                    .rg_state
-                    .lock()
-                    .await
-                    .assign_segment_to_reader(&self.id)
-                    .await
-                    .expect("Error while waiting for segments to be assigned")
+                    .retry_rg_state("assign segment", || async {
+                        self.rg_state
+                            .lock()
+                            .await
+                            .assign_segment_to_reader(&self.id)
+                            .await
+                    })
+                    .await?
                 {
                     debug!("Acquiring segment {:?} for reader {:?}", seg, self.id);
                     new_segments.push(seg);
@@ -718,7 +1677,7 @@ impl EventReader {
                 }
             }
             debug!("Segments acquired by reader {:?} is {:?}", self.id, new_segments);
-            Some(new_segments)
+            Ok(Some(new_segments))
         }
     }
 
@@ -731,15 +1690,7 @@ impl EventReader {
                 read_offset: offset.read, // read offset should be same as start_offset.
                 ..Default::default()
             };
-            let (tx_drop_fetch, rx_drop_fetch) = oneshot::channel();
-            tokio::spawn(SegmentSlice::get_segment_data(
-                seg.clone(),
-                meta.start_offset,
-                self.tx.clone(),
-                rx_drop_fetch,
-                self.factory.clone(),
-            ));
-            self.meta.add_stop_reading_tx(seg, tx_drop_fetch);
+            self.spawn_read_task(seg, meta.start_offset);
             // update map with newer segments.
             self.meta.add_slices(meta);
         }
@@ -757,74 +1708,257 @@ impl EventReader {
 
     // Fetch the successors for a given segment from the controller.
     async fn get_successors(
-        &mut self,
-        completed_scoped_segment: &str,
-    ) -> ImHashMap<SegmentWithRange, Vec<Segment>> {
-        let completed_scoped_segment = ScopedSegment::from(completed_scoped_segment);
+        &self,
+        completed_scoped_segment: &ScopedSegment,
+    ) -> Result<StreamSegmentsWithPredecessors, ReaderGroupStateError> {
         self.factory
             .get_controller_client()
-            .get_successors(&completed_scoped_segment)
+            .get_successors(completed_scoped_segment)
             .await
-            .expect("Failed to fetch successors of the segment")
-            .segment_with_predecessors
+            .map_err(|err| ReaderGroupStateError::ControllerRequestError {
+                error_msg: format!("fetch successors of segment {:?}", completed_scoped_segment),
+                err,
+            })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::client_factory::ClientFactory;
-    use crate::error::SynchronizerError;
-    use crate::event_reader::{EventReader, SegmentReadResult};
-    use crate::reader_group::reader_group_state::ReaderGroupStateError;
-    use crate::segment_slice::{SegmentDataBuffer, SegmentSlice, SliceMetadata};
-    use bytes::{BufMut, BytesMut};
-    use mockall::predicate;
-    use mockall::predicate::*;
-    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
-    use pravega_client_shared::{Reader, Scope, ScopedSegment, ScopedStream, Stream};
-    use pravega_wire_protocol::commands::{Command, EventCommand};
-    use std::collections::HashMap;
-    use std::iter;
-    use std::sync::Arc;
-    use tokio::sync::mpsc::Sender;
-    use tokio::sync::oneshot;
-    use tokio::sync::oneshot::error::TryRecvError;
-    use tokio::sync::{mpsc, Mutex};
-    use tokio::time::{sleep, Duration};
-    use tracing::Level;
+///
+/// A cloneable handle to an EventReader which lets several tasks consume segments of the reader
+/// concurrently. Each task acquires a SegmentSlice of a different segment, the data received for a
+/// segment whose slice is out for consumption is kept until the slice is returned. The reader
+/// is only locked while its state is updated, tasks waiting for data do not block the tasks
+/// releasing their slices.
+///
+/// ```no_run
+/// use pravega_client::event_reader::EventReader;
+///
+/// async fn consume(reader: EventReader) {
+///     let handle = reader.into_handle();
+///     for _ in 0..4 {
+///         let handle = handle.clone();
+///         tokio::spawn(async move {
+///             while let Ok(slice) = handle.acquire_segment().await {
+///                 if let Some(slice) = slice {
+///                     for event in slice {
+///                         println!("Event read is {:?}", event);
+///                     }
+///                 }
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+#[derive(Clone)]
+pub struct EventReaderHandle {
+    reader: Arc<Mutex<EventReader>>,
+    rx: Arc<Mutex<Receiver<SegmentReadResultWithEpoch>>>,
+}
 
-    /*
-     This test verifies EventReader reads from a stream where only one segment has data while the other segment is empty.
-    */
-    #[test]
-    fn test_read_events_single_segment() {
-        const NUM_EVENTS: usize = 100;
-        let (tx, rx) = mpsc::channel(1);
-        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
-        let cf = ClientFactory::new(
-            ClientConfigBuilder::default()
-                .controller_uri(MOCK_CONTROLLER_URI)
-                .build()
-                .unwrap(),
-        );
+impl EventReaderHandle {
+    ///
+    /// Return a SegmentSlice of a segment which is not out for consumption by another task.
+    /// See [`EventReader::acquire_segment`].
+    ///
+    pub async fn acquire_segment(&self) -> Result<Option<SegmentSlice>, EventReaderError> {
+        let acquire_timeout = self.reader.lock().await.config.get_acquire_timeout();
+        let start = Instant::now();
+        loop {
+            if let Some(slice) = self.reader.lock().await.try_acquire_segment().await? {
+                return Ok(Some(slice));
+            }
+            let remaining = match acquire_timeout.checked_sub(start.elapsed()) {
+                Some(remaining) => remaining,
+                None => return Ok(None),
+            };
+            // wait for data without holding the reader.
+            let read_result = match timeout(remaining, self.rx.lock().await.recv()).await {
+                Ok(Some(read_result)) => read_result,
+                Ok(None) => return Ok(None),
+                Err(_) => return Ok(None),
+            };
+            if let Some(slice) = self
+                .reader
+                .lock()
+                .await
+                .process_read_result_concurrently(read_result)
+                .await?
+            {
+                return Ok(Some(slice));
+            }
+        }
+    }
 
-        // simulate data being received from Segment store.
-        let _guard = cf.get_runtime().enter();
-        tokio::spawn(generate_variable_size_events(
-            tx.clone(),
-            10,
-            NUM_EVENTS,
-            0,
-            false,
-        ));
+    ///
+    /// Release a partially read segment slice back to the reader, see [`EventReader::release_segment`].
+    ///
+    pub async fn release_segment(&self, slice: SegmentSlice) -> Result<(), ReaderGroupStateError> {
+        self.reader.lock().await.release_segment(slice).await
+    }
 
-        // simulate initialization of a Reader
+    ///
+    /// Release a segment slice at the given offset, see [`EventReader::release_segment_at`].
+    ///
+    pub async fn release_segment_at(
+        &self,
+        slice: SegmentSlice,
+        offset: i64,
+    ) -> Result<(), ReaderGroupStateError> {
+        self.reader.lock().await.release_segment_at(slice, offset).await
+    }
+
+    ///
+    /// Publish the read offsets of the reader, see [`EventReader::publish_positions`].
+    ///
+    pub async fn publish_positions(&self) -> Result<(), ReaderGroupStateError> {
+        self.reader.lock().await.publish_positions().await
+    }
+
+    ///
+    /// Pause reading from a segment, see [`EventReader::pause`].
+    ///
+    pub async fn pause(&self, segment: &ScopedSegment) -> bool {
+        self.reader.lock().await.pause(segment)
+    }
+
+    ///
+    /// Resume reading from a paused segment, see [`EventReader::resume`].
+    ///
+    pub async fn resume(&self, segment: &ScopedSegment) -> bool {
+        self.reader.lock().await.resume(segment)
+    }
+
+    ///
+    /// Mark the reader as offline, see [`EventReader::reader_offline`].
+    ///
+    pub async fn reader_offline(&self) {
+        self.reader.lock().await.reader_offline().await
+    }
+
+    ///
+    /// Mark the reader as offline and return the error if the ReaderGroup cannot be updated,
+    /// see [`EventReader::close`].
+    ///
+    pub async fn close(&self) -> Result<(), ReaderGroupStateError> {
+        self.reader.lock().await.close().await
+    }
+}
+
+//
+// Perform an update of the reader group state on behalf of a reader, the update is retried a few
+// times before the error is returned.
+//
+async fn retry_rg_state<T, E, F, Fut>(
+    factory: &ClientFactory,
+    reader: &Reader,
+    operation_name: &str,
+    mut operation: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let retry_policy = factory
+        .get_config()
+        .retry_policy
+        .max_tries(READER_GROUP_STATE_RETRIES);
+    retry_async(retry_policy, || {
+        let result = operation();
+        async move {
+            match result.await {
+                Ok(value) => RetryResult::Success(value),
+                Err(e) => {
+                    warn!(
+                        "failed to {} for reader {}, retrying: {}",
+                        operation_name, reader, e
+                    );
+                    RetryResult::Retry(e)
+                }
+            }
+        }
+    })
+    .await
+    .map_err(|e| e.error)
+}
+
+impl Drop for EventReader {
+    fn drop(&mut self) {
+        if self.closed {
+            return;
+        }
+        info!(
+            "reader {} is dropped without being closed, putting it offline",
+            self.id
+        );
+        self.meta.stop_reading_all();
+        self.meta.close_all_slice_return_channel();
+        let offset_map = self.meta.committed_offsets();
+        let rg_state = self.rg_state.clone();
+        let id = self.id.clone();
+        self.factory.get_runtime().spawn(async move {
+            if let Err(e) = rg_state.lock().await.remove_reader(&id, offset_map).await {
+                error!("failed to put dropped reader {} offline: {}", id, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ack_tracker::AckHandle;
+    use crate::client_factory::ClientFactory;
+    use crate::create_stream;
+    use crate::error::SynchronizerError;
+    use crate::event_reader::{EventReader, SegmentReadResultWithEpoch};
+    use crate::reader_group_config::ReaderConfigBuilder;
+    use crate::segment_slice::{SegmentDataBuffer, SegmentSlice, SliceMetadata};
+    use bytes::{BufMut, BytesMut};
+    use mockall::predicate;
+    use mockall::predicate::*;
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use pravega_client_shared::{Reader, Scope, ScopedSegment, ScopedStream, Stream};
+    use pravega_wire_protocol::commands::{Command, EventCommand};
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::iter;
+    use std::sync::Arc;
+    use tokio::sync::mpsc::Sender;
+    use tokio::sync::oneshot;
+    use tokio::sync::oneshot::error::TryRecvError;
+    use tokio::sync::{mpsc, Mutex};
+    use tokio::time::{sleep, Duration};
+    use tracing::Level;
+
+    /*
+     This test verifies EventReader reads from a stream where only one segment has data while the other segment is empty.
+    */
+    #[test]
+    fn test_read_events_single_segment() {
+        const NUM_EVENTS: usize = 100;
+        let (tx, rx) = mpsc::channel(1);
+        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
+        let cf = create_client_factory();
+
+        // simulate data being received from Segment store.
+        let _guard = cf.get_runtime().enter();
+        tokio::spawn(generate_variable_size_events(
+            tx.clone(),
+            10,
+            NUM_EVENTS,
+            0,
+            false,
+        ));
+
+        // simulate initialization of a Reader
         let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
-        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
         rg_mock
             .expect_compute_segments_to_acquire_or_release()
-            .return_const(0 as isize);
+            .returning(|_| Ok(0 as isize));
         // create a new Event Reader with the segment slice data.
         let mut reader = EventReader::init_event_reader(
             Arc::new(Mutex::new(rg_mock)),
@@ -840,7 +1974,11 @@ mod tests {
         let mut event_size = 0;
 
         // Attempt to acquire a segment.
-        while let Some(mut slice) = cf.get_runtime().block_on(reader.acquire_segment()) {
+        while let Some(mut slice) = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+        {
             loop {
                 if let Some(event) = slice.next() {
                     println!("Read event {:?}", event);
@@ -868,12 +2006,7 @@ mod tests {
         const NUM_EVENTS: usize = 10;
         let (tx, rx) = mpsc::channel(1);
         tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
-        let cf = ClientFactory::new(
-            ClientConfigBuilder::default()
-                .controller_uri(MOCK_CONTROLLER_URI)
-                .build()
-                .unwrap(),
-        );
+        let cf = create_client_factory();
 
         // simulate data being received from Segment store.
         let _guard = cf.get_runtime().enter();
@@ -887,11 +2020,16 @@ mod tests {
 
         // simulate initialization of a Reader
         let init_segments = vec![create_segment_slice(0)];
-        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
         rg_mock
             .expect_compute_segments_to_acquire_or_release()
             .with(predicate::eq(Reader::from("r1".to_string())))
-            .return_const(1 as isize);
+            .returning(|_| Ok(1 as isize));
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock.expect_heartbeat().returning(|_| Ok(()));
+        rg_mock
+            .expect_get_segment_assignment_strategy()
+            .returning(|| Ok(SegmentAssignmentStrategy::SegmentCount));
 
         // mock rg_state.assign_segment_to_reader
         let res: Result<Option<ScopedSegment>, ReaderGroupStateError> =
@@ -904,11 +2042,10 @@ mod tests {
         let mut new_current_segments: HashSet<(ScopedSegment, Offset)> = HashSet::new();
         new_current_segments.insert((ScopedSegment::from("scope/test/1.#epoch.0"), Offset::new(0)));
         new_current_segments.insert((ScopedSegment::from("scope/test/0.#epoch.0"), Offset::new(0)));
-        let res: Result<HashSet<(ScopedSegment, Offset)>, SynchronizerError> = Ok(new_current_segments);
         rg_mock
             .expect_get_segments_for_reader()
             .with(predicate::eq(Reader::from("r1".to_string())))
-            .return_once(move |_| res);
+            .returning(move |_| Ok(new_current_segments.clone()));
 
         // simulate data being received from Segment store.
         tokio::spawn(generate_variable_size_events(
@@ -925,268 +2062,1099 @@ mod tests {
             Arc::new(Mutex::new(rg_mock)),
             Reader::from("r1".to_string()),
             cf.clone(),
-            tx.clone(),
+            tx.clone(),
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+        reader.set_last_acquire_release_time(before_time);
+
+        let mut event_count = 0;
+
+        // Attempt to acquire a segment.
+        while let Some(mut slice) = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+        {
+            loop {
+                if let Some(event) = slice.next() {
+                    println!("Read event {:?}", event);
+                    assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+                    event_count += 1;
+                } else {
+                    println!(
+                        "Finished reading from segment {:?}, segment is auto released",
+                        slice.meta.scoped_segment
+                    );
+                    break; // try to acquire the next segment.
+                }
+            }
+            if event_count == NUM_EVENTS + NUM_EVENTS {
+                // all events have been read. Exit test.
+                break;
+            }
+        }
+        assert_eq!(event_count, NUM_EVENTS + NUM_EVENTS);
+    }
+
+    /*
+      This test verifies an EventReader reading from a stream where both the segments are sending data.
+    */
+    #[test]
+    fn test_read_events_multiple_segments() {
+        const NUM_EVENTS: usize = 100;
+        let (tx, rx) = mpsc::channel(1);
+        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
+        let cf = create_client_factory();
+
+        // simulate data being received from Segment store. 2 async tasks pumping in data.
+        let _guard = cf.get_runtime().enter();
+        tokio::spawn(generate_variable_size_events(
+            tx.clone(),
+            100,
+            NUM_EVENTS,
+            0,
+            false,
+        ));
+        //simulate a delay with data received by this segment.
+        tokio::spawn(generate_variable_size_events(
+            tx.clone(),
+            100,
+            NUM_EVENTS,
+            1,
+            true,
+        ));
+
+        // simulate initialization of a Reader
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock
+            .expect_compute_segments_to_acquire_or_release()
+            .returning(|_| Ok(0 as isize));
+        // create a new Event Reader with the segment slice data.
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx.clone(),
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+
+        let mut event_count_per_segment: HashMap<String, usize> = HashMap::new();
+
+        let mut total_events_read = 0;
+        // Attempt to acquire a segment.
+        while let Some(mut slice) = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+        {
+            let segment = slice.meta.scoped_segment.clone();
+            println!("Received Segment Slice {:?}", segment);
+            let mut event_count = 0;
+            loop {
+                if let Some(event) = slice.next() {
+                    println!("Read event {:?}", event);
+                    assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+                    event_count += 1;
+                } else {
+                    println!(
+                        "Finished reading from segment {:?}, segment is auto released",
+                        slice.meta.scoped_segment
+                    );
+                    break; // try to acquire the next segment.
+                }
+            }
+            total_events_read += event_count;
+            *event_count_per_segment
+                .entry(segment.clone())
+                .or_insert(event_count) += event_count;
+            if total_events_read == NUM_EVENTS * 2 {
+                // all events have been read. Exit test.
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_return_slice() {
+        const NUM_EVENTS: usize = 2;
+        let (tx, rx) = mpsc::channel(1);
+        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
+        let cf = create_client_factory();
+
+        // simulate data being received from Segment store.
+        let _guard = cf.get_runtime().enter();
+        tokio::spawn(generate_variable_size_events(
+            tx.clone(),
+            10,
+            NUM_EVENTS,
+            0,
+            false,
+        ));
+
+        // simulate initialization of a Reader
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock
+            .expect_compute_segments_to_acquire_or_release()
+            .returning(|_| Ok(0 as isize));
+
+        // create a new Event Reader with the segment slice data.
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx.clone(),
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+
+        // acquire a segment
+        let mut slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+
+        // read an event.
+        let event = slice.next().unwrap();
+        assert_eq!(event.value.len(), 1);
+        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+        assert_eq!(event.offset_in_segment, 0); // first event.
+
+        // release the segment slice.
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+
+        // acquire the next segment
+        let slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+
+        //Do not read, simply return it back.
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+
+        // Try acquiring the segment again.
+        let mut slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+        // Verify a partial event being present. This implies
+        let event = slice.next().unwrap();
+        assert_eq!(event.value.len(), 2);
+        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+        assert_eq!(event.offset_in_segment, 8 + 1); // first event.
+    }
+
+    #[test]
+    fn test_return_slice_at_offset() {
+        const NUM_EVENTS: usize = 2;
+        let (tx, rx) = mpsc::channel(1);
+        let (stop_tx, stop_rx) = oneshot::channel();
+        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
+        let cf = create_client_factory();
+
+        // simulate data being received from Segment store.
+        let _guard = cf.get_runtime().enter();
+        tokio::spawn(generate_constant_size_events(
+            tx.clone(),
+            20,
+            NUM_EVENTS,
+            0,
+            0,
+            false,
+            stop_rx,
+        ));
+        let mut stop_reading_map: HashMap<ScopedSegment, oneshot::Sender<()>> = HashMap::new();
+        stop_reading_map.insert(ScopedSegment::from("scope/test/0.#epoch.0"), stop_tx);
+
+        // simulate initialization of a Reader
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock
+            .expect_compute_segments_to_acquire_or_release()
+            .returning(|_| Ok(0 as isize));
+        // create a new Event Reader with the segment slice data.
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx.clone(),
+            rx,
+            create_slice_map(init_segments),
+            stop_reading_map,
+        );
+
+        // acquire a segment
+        let mut slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+
+        // read an event.
+        let event = slice.next().unwrap();
+        assert_eq!(event.value.len(), 1);
+        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+        assert_eq!(event.offset_in_segment, 0); // first event.
+
+        let result = slice.next();
+        assert!(result.is_some());
+        let event = result.unwrap();
+        assert_eq!(event.value.len(), 1);
+        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+        assert_eq!(event.offset_in_segment, 9); // second event.
+
+        // release the segment slice.
+        cf.get_runtime()
+            .block_on(reader.release_segment_at(slice, 0))
+            .expect("release segment");
+
+        // simulate a segment read at offset 0.
+        let (_stop_tx, stop_rx) = oneshot::channel();
+        let read_epoch = reader.get_read_epoch(&ScopedSegment::from("scope/test/0.#epoch.0"));
+        tokio::spawn(generate_constant_size_events(
+            tx.clone(),
+            20,
+            NUM_EVENTS,
+            0,
+            read_epoch,
+            false,
+            stop_rx,
+        ));
+
+        // acquire the next segment
+        let mut slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+        // Verify a partial event being present. This implies
+        let event = slice.next().unwrap();
+        assert_eq!(event.value.len(), 1);
+        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
+        assert_eq!(event.offset_in_segment, 0); // first event.
+    }
+
+    #[test]
+    fn test_reader_group_reset() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+
+        // simulate a reader group which has been reset after the reader was initialized.
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(1u64));
+        rg_mock.expect_heartbeat().returning(|_| Ok(()));
+        rg_mock
+            .expect_get_segments_for_reader()
+            .returning(|_| Ok(HashSet::new()));
+        rg_mock.expect_is_end_of_stream().returning(|| Ok(false));
+        rg_mock
+            .expect_get_segment_assignment_strategy()
+            .returning(|| Ok(SegmentAssignmentStrategy::SegmentCount));
+        rg_mock
+            .expect_compute_segments_to_acquire_or_release()
+            .returning(|_| Ok(0 as isize));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+        reader.set_last_acquire_release_time(Instant::now() - Duration::from_secs(15));
+
+        // the reader drops its segments of the previous generation.
+        assert!(cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .is_none());
+        assert!(reader.meta.slices.is_empty());
+        assert_eq!(reader.meta.generation, 1);
+    }
+
+    #[test]
+    fn test_reader_group_reset_on_release() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+        tokio::spawn(generate_variable_size_events(tx.clone(), 1024, 10, 0, false));
+
+        // the reader group is reset while the slice is out for consumption, the generation is only
+        // fetched once the rebalance interval elapses.
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().times(1).returning(|| Ok(1u64));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx.clone(),
+            rx,
+            create_slice_map(vec![create_segment_slice(0)]),
+            HashMap::new(),
+        );
+        let slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .expect("segment slice");
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+        assert_eq!(reader.meta.generation, 0);
+        let slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .expect("segment slice");
+
+        // the reset is noticed at the next rebalance and the returned slice is ignored.
+        reader.meta.last_segment_release = Instant::now() - Duration::from_secs(15);
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+        assert!(reader.meta.slices.is_empty());
+        assert!(reader.meta.slices_dished_out.is_empty());
+        assert_eq!(reader.meta.generation, 1);
+    }
+
+    #[test]
+    fn test_reader_group_state_failure() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+
+        // the reader group state cannot be fetched while the reader rebalances its segments.
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock
+            .expect_get_generation()
+            .times(READER_GROUP_STATE_RETRIES as usize + 1)
+            .returning(|| {
+                Err(ReaderGroupStateError::SyncError {
+                    error_msg: "fetch generation".to_string(),
+                    source: SynchronizerError::SyncUpdateError {
+                        error_msg: "table segment unavailable".to_string(),
+                    },
+                })
+            });
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+        reader.set_last_acquire_release_time(Instant::now() - Duration::from_secs(15));
+
+        // the error is returned and the reader keeps its segments.
+        let res = cf.get_runtime().block_on(reader.acquire_segment());
+        assert!(matches!(
+            res,
+            Err(EventReaderError::ReaderGroupStateFailure { .. })
+        ));
+        assert_eq!(reader.meta.slices.len(), 2);
+    }
+
+    #[test]
+    fn test_reader_put_offline() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+
+        // the lease of the reader has expired and other readers have put it offline.
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock.expect_heartbeat().returning(|reader| {
+            Err(ReaderGroupStateError::ReaderOffline {
+                reader: reader.to_string(),
+            })
+        });
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+        reader.set_last_acquire_release_time(Instant::now() - Duration::from_secs(15));
+
+        // the reader drops the segments which now belong to other readers.
+        let res = cf.get_runtime().block_on(reader.acquire_segment());
+        assert!(matches!(
+            res,
+            Err(EventReaderError::ReaderGroupStateFailure {
+                source: ReaderGroupStateError::ReaderOffline { .. }
+            })
+        ));
+        assert!(reader.meta.slices.is_empty());
+    }
+
+    #[test]
+    fn test_drop_unassigned_segments() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+
+        // the stream of segment 1 has been removed from the reader group.
+        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock.expect_heartbeat().returning(|_| Ok(()));
+        rg_mock.expect_get_segments_for_reader().returning(|_| {
+            let mut segments = HashSet::new();
+            segments.insert((ScopedSegment::from("scope/test/0.#epoch.0"), Offset::new(0)));
+            Ok(segments)
+        });
+        rg_mock
+            .expect_get_segment_assignment_strategy()
+            .returning(|| Ok(SegmentAssignmentStrategy::SegmentCount));
+        rg_mock
+            .expect_compute_segments_to_acquire_or_release()
+            .returning(|_| Ok(0 as isize));
+        rg_mock.expect_is_end_of_stream().returning(|| Ok(false));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+        reader.set_last_acquire_release_time(Instant::now() - Duration::from_secs(15));
+
+        // the reader drops the removed segment and keeps the other one.
+        assert!(cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .is_none());
+        assert_eq!(reader.meta.slices.len(), 1);
+        assert!(reader
+            .meta
+            .slices
+            .contains_key(&ScopedSegment::from("scope/test/0.#epoch.0")));
+    }
+
+    #[test]
+    fn test_end_of_stream() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+
+        // the reader owns no segments and the reader group has read all the sealed streams.
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock.expect_is_end_of_stream().times(1).returning(|| Ok(true));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            HashMap::new(),
+            HashMap::new(),
+        );
+        let res = cf.get_runtime().block_on(reader.acquire_segment());
+        assert!(matches!(res, Err(EventReaderError::EndOfStream)));
+    }
+
+    #[test]
+    fn test_publish_positions() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+
+        let mut slice0 = create_segment_slice(0);
+        slice0.meta.read_offset = 10;
+        let init_segments = vec![slice0, create_segment_slice(1)];
+        let mut expected: HashMap<ScopedSegment, Offset> = HashMap::new();
+        expected.insert(ScopedSegment::from("scope/test/0.#epoch.0"), Offset::new(10));
+        expected.insert(ScopedSegment::from("scope/test/1.#epoch.0"), Offset::new(0));
+
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock
+            .expect_update_reader_positions()
+            .with(
+                predicate::eq(Reader::from("r1".to_string())),
+                predicate::eq(expected),
+            )
+            .times(1)
+            .return_once(|_, _| Ok(()));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(init_segments),
+            HashMap::new(),
+        );
+        cf.get_runtime()
+            .block_on(reader.publish_positions())
+            .expect("publish positions");
+    }
+
+    #[test]
+    fn test_publish_acknowledged_positions() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+        // events of length 1, 2 and 3 are at the offsets 0, 9 and 19 of the segment.
+        tokio::spawn(generate_variable_size_events(tx.clone(), 1024, 3, 0, false));
+
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+        let mut pending: HashMap<ScopedSegment, Offset> = HashMap::new();
+        pending.insert(segment.clone(), Offset::new(0));
+        let mut acknowledged: HashMap<ScopedSegment, Offset> = HashMap::new();
+        acknowledged.insert(segment.clone(), Offset::new(30));
+
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock
+            .expect_update_reader_positions()
+            .with(
+                predicate::eq(Reader::from("r1".to_string())),
+                predicate::eq(pending),
+            )
+            .times(1)
+            .return_once(|_, _| Ok(()));
+        rg_mock
+            .expect_update_reader_positions()
+            .with(
+                predicate::eq(Reader::from("r1".to_string())),
+                predicate::eq(acknowledged),
+            )
+            .times(1)
+            .return_once(|_, _| Ok(()));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(vec![create_segment_slice(0)]),
+            HashMap::new(),
+        );
+        reader.meta.ack_tracker = Some(Arc::new(AckTracker::default()));
+
+        let mut slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .expect("segment slice");
+        let mut handles: Vec<AckHandle> = slice.by_ref().map(|event| event.ack_handle.unwrap()).collect();
+        assert_eq!(handles.len(), 3);
+        let first = handles.remove(0);
+        handles.into_iter().for_each(AckHandle::ack);
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+
+        // the first event has not been acknowledged.
+        cf.get_runtime()
+            .block_on(reader.publish_positions())
+            .expect("publish positions");
+        first.ack();
+        cf.get_runtime()
+            .block_on(reader.publish_positions())
+            .expect("publish positions");
+    }
+
+    #[test]
+    fn test_acquire_segments_concurrently() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let _guard = cf.get_runtime().enter();
+        tokio::spawn(generate_variable_size_events(tx.clone(), 10, 20, 0, false));
+        tokio::spawn(generate_variable_size_events(tx.clone(), 10, 20, 1, false));
+
+        let mut rg_mock = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        let reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(vec![create_segment_slice(0), create_segment_slice(1)]),
+            HashMap::new(),
+        );
+        let handle = reader.into_handle();
+        let mut events_read: HashMap<String, usize> = HashMap::new();
+        cf.get_runtime().block_on(async {
+            // the second slice is acquired while the first one is out for consumption.
+            let mut slice1 = handle.acquire_segment().await.unwrap().unwrap();
+            let mut slice2 = handle.clone().acquire_segment().await.unwrap().unwrap();
+            assert_ne!(slice1.meta.scoped_segment, slice2.meta.scoped_segment);
+            *events_read.entry(slice1.meta.scoped_segment.clone()).or_default() += slice1.by_ref().count();
+            *events_read.entry(slice2.meta.scoped_segment.clone()).or_default() += slice2.by_ref().count();
+            handle.release_segment(slice1).await.expect("release segment");
+            drop(slice2);
+
+            while let Some(mut slice) = handle.acquire_segment().await.unwrap() {
+                *events_read.entry(slice.meta.scoped_segment.clone()).or_default() += slice.by_ref().count();
+            }
+        });
+        assert_eq!(events_read.get("scope/test/0.#epoch.0"), Some(&20));
+        assert_eq!(events_read.get("scope/test/1.#epoch.0"), Some(&20));
+    }
+
+    #[test]
+    fn test_reader_offline_on_drop() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let (offline_tx, offline_rx) = std::sync::mpsc::channel();
+        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
+        rg_mock.expect_remove_reader().returning(move |reader, offsets| {
+            offline_tx.send((reader.clone(), offsets)).expect("send offsets");
+            Ok(())
+        });
+        let mut slice = create_segment_slice(0);
+        slice.meta.read_offset = 10;
+        let reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(vec![slice]),
+            HashMap::new(),
+        );
+
+        // the reader is put offline at its read offsets once it is dropped.
+        drop(reader);
+        let (reader, offsets) = offline_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("reader is put offline");
+        assert_eq!(reader, Reader::from("r1".to_string()));
+        assert_eq!(
+            offsets.get(&ScopedSegment::from("scope/test/0")),
+            Some(&Offset::new(10))
+        );
+    }
+
+    #[test]
+    fn test_close_reader() {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let (offline_tx, offline_rx) = std::sync::mpsc::channel();
+        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
+        rg_mock.expect_remove_reader().times(1).returning(|_, _| {
+            Err(ReaderGroupStateError::SyncError {
+                error_msg: "remove reader".to_string(),
+                source: SynchronizerError::SyncUpdateError {
+                    error_msg: "conflict".to_string(),
+                },
+            })
+        });
+        rg_mock
+            .expect_remove_reader()
+            .times(1)
+            .returning(move |_, offsets| {
+                offline_tx.send(offsets).expect("send offsets");
+                Ok(())
+            });
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
             rx,
-            create_slice_map(init_segments),
+            create_slice_map(vec![create_segment_slice(0)]),
             HashMap::new(),
         );
-        reader.set_last_acquire_release_time(before_time);
 
-        let mut event_count = 0;
+        // the reader keeps its segments if it cannot be removed from the reader group.
+        assert!(cf.get_runtime().block_on(reader.close()).is_err());
+        cf.get_runtime().block_on(reader.close()).expect("close reader");
+        let offsets = offline_rx.recv().expect("reader is put offline");
+        assert!(offsets.contains_key(&ScopedSegment::from("scope/test/0")));
 
-        // Attempt to acquire a segment.
-        while let Some(mut slice) = cf.get_runtime().block_on(reader.acquire_segment()) {
-            loop {
-                if let Some(event) = slice.next() {
-                    println!("Read event {:?}", event);
-                    assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-                    event_count += 1;
-                } else {
-                    println!(
-                        "Finished reading from segment {:?}, segment is auto released",
-                        slice.meta.scoped_segment
-                    );
-                    break; // try to acquire the next segment.
-                }
-            }
-            if event_count == NUM_EVENTS + NUM_EVENTS {
-                // all events have been read. Exit test.
-                break;
-            }
-        }
-        assert_eq!(event_count, NUM_EVENTS + NUM_EVENTS);
+        // a closed reader is not put offline again.
+        cf.get_runtime().block_on(reader.close()).expect("close reader");
+        drop(reader);
+        assert!(offline_rx.recv_timeout(Duration::from_millis(100)).is_err());
     }
 
-    /*
-      This test verifies an EventReader reading from a stream where both the segments are sending data.
-    */
     #[test]
-    fn test_read_events_multiple_segments() {
-        const NUM_EVENTS: usize = 100;
+    fn test_release_segment_failure() {
         let (tx, rx) = mpsc::channel(1);
-        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
-        let cf = ClientFactory::new(
-            ClientConfigBuilder::default()
-                .controller_uri(MOCK_CONTROLLER_URI)
-                .build()
-                .unwrap(),
-        );
-
-        // simulate data being received from Segment store. 2 async tasks pumping in data.
+        let cf = create_client_factory();
         let _guard = cf.get_runtime().enter();
-        tokio::spawn(generate_variable_size_events(
-            tx.clone(),
-            100,
-            NUM_EVENTS,
-            0,
-            false,
-        ));
-        //simulate a delay with data received by this segment.
-        tokio::spawn(generate_variable_size_events(
-            tx.clone(),
-            100,
-            NUM_EVENTS,
-            1,
-            true,
-        ));
+        tokio::spawn(generate_variable_size_events(tx.clone(), 1024, 10, 0, false));
 
-        // simulate initialization of a Reader
-        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
-        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
         rg_mock
             .expect_compute_segments_to_acquire_or_release()
-            .return_const(0 as isize);
-        // create a new Event Reader with the segment slice data.
+            .returning(|_| Ok(-1isize));
+        rg_mock.expect_get_segments_for_reader().returning(|_| {
+            let mut segments = HashSet::new();
+            segments.insert((ScopedSegment::from("scope/test/0.#epoch.0"), Offset::new(0)));
+            Ok(segments)
+        });
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock.expect_heartbeat().returning(|_| Ok(()));
+        rg_mock
+            .expect_get_segment_assignment_strategy()
+            .returning(|| Ok(SegmentAssignmentStrategy::SegmentCount));
+        // the update fails even after it is retried.
+        rg_mock
+            .expect_release_segment()
+            .times((READER_GROUP_STATE_RETRIES + 1) as usize)
+            .returning(|_, _, _| {
+                Err(ReaderGroupStateError::SyncError {
+                    error_msg: "release segment".to_string(),
+                    source: SynchronizerError::SyncUpdateError {
+                        error_msg: "conflict".to_string(),
+                    },
+                })
+            });
+        rg_mock
+            .expect_release_segment()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
         let mut reader = EventReader::init_event_reader(
             Arc::new(Mutex::new(rg_mock)),
             Reader::from("r1".to_string()),
             cf.clone(),
             tx.clone(),
             rx,
-            create_slice_map(init_segments),
+            create_slice_map(vec![create_segment_slice(0)]),
             HashMap::new(),
         );
-
-        let mut event_count_per_segment: HashMap<String, usize> = HashMap::new();
-
-        let mut total_events_read = 0;
-        // Attempt to acquire a segment.
-        while let Some(mut slice) = cf.get_runtime().block_on(reader.acquire_segment()) {
-            let segment = slice.meta.scoped_segment.clone();
-            println!("Received Segment Slice {:?}", segment);
-            let mut event_count = 0;
-            loop {
-                if let Some(event) = slice.next() {
-                    println!("Read event {:?}", event);
-                    assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-                    event_count += 1;
-                } else {
-                    println!(
-                        "Finished reading from segment {:?}, segment is auto released",
-                        slice.meta.scoped_segment
-                    );
-                    break; // try to acquire the next segment.
-                }
-            }
-            total_events_read += event_count;
-            *event_count_per_segment
-                .entry(segment.clone())
-                .or_insert(event_count) += event_count;
-            if total_events_read == NUM_EVENTS * 2 {
-                // all events have been read. Exit test.
-                break;
-            }
-        }
+        reader.set_last_acquire_release_time(Instant::now() - Duration::from_secs(15));
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+
+        // the segment stays assigned to the reader if it cannot be released.
+        let slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+        assert!(cf.get_runtime().block_on(reader.release_segment(slice)).is_err());
+        assert!(reader.meta.slices.contains_key(&segment));
+
+        let slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .unwrap();
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+        assert!(!reader.meta.slices.contains_key(&segment));
     }
 
     #[test]
-    fn test_return_slice() {
-        const NUM_EVENTS: usize = 2;
+    fn test_release_segment_with_buffered_reads() {
         let (tx, rx) = mpsc::channel(1);
-        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
-        let cf = ClientFactory::new(
-            ClientConfigBuilder::default()
-                .controller_uri(MOCK_CONTROLLER_URI)
-                .build()
-                .unwrap(),
-        );
-
-        // simulate data being received from Segment store.
-        let _guard = cf.get_runtime().enter();
-        tokio::spawn(generate_variable_size_events(
-            tx.clone(),
-            10,
-            NUM_EVENTS,
-            0,
-            false,
-        ));
-
-        // simulate initialization of a Reader
-        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
-
-        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
+        let cf = create_client_factory();
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock.expect_heartbeat().returning(|_| Ok(()));
         rg_mock
             .expect_compute_segments_to_acquire_or_release()
-            .return_const(0 as isize);
-
-        // create a new Event Reader with the segment slice data.
+            .returning(|_| Ok(-1isize));
+        rg_mock.expect_release_segment().returning(|_, _, _| Ok(()));
         let mut reader = EventReader::init_event_reader(
             Arc::new(Mutex::new(rg_mock)),
             Reader::from("r1".to_string()),
             cf.clone(),
-            tx.clone(),
+            tx,
             rx,
-            create_slice_map(init_segments),
+            create_slice_map(vec![create_segment_slice(0)]),
             HashMap::new(),
         );
-
-        // acquire a segment
-        let mut slice = cf.get_runtime().block_on(reader.acquire_segment()).unwrap();
-
-        // read an event.
-        let event = slice.next().unwrap();
-        assert_eq!(event.value.len(), 1);
-        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-        assert_eq!(event.offset_in_segment, 0); // first event.
-
-        // release the segment slice.
-        cf.get_runtime().block_on(reader.release_segment(slice));
-
-        // acquire the next segment
-        let slice = cf.get_runtime().block_on(reader.acquire_segment()).unwrap();
-
-        //Do not read, simply return it back.
-        cf.get_runtime().block_on(reader.release_segment(slice));
-
-        // Try acquiring the segment again.
-        let mut slice = cf.get_runtime().block_on(reader.acquire_segment()).unwrap();
-        // Verify a partial event being present. This implies
-        let event = slice.next().unwrap();
-        assert_eq!(event.value.len(), 2);
-        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-        assert_eq!(event.offset_in_segment, 8 + 1); // first event.
+        reader.set_last_acquire_release_time(Instant::now() - Duration::from_secs(15));
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+        let slice_meta = reader.meta.slices.remove(&segment).expect("slice meta");
+        let slice = reader.dish_out_slice(slice_meta);
+
+        // data prefetched while the slice is out for consumption holds its permits.
+        let max_prefetch = ReaderConfig::default().get_max_prefetch_bytes();
+        cf.get_runtime()
+            .block_on(reader.meta.prefetch_limit.acquire_many(10))
+            .expect("acquire permits")
+            .forget();
+        reader.meta.buffer_read(
+            segment.clone(),
+            Ok(SegmentDataBuffer {
+                segment: segment.to_string(),
+                offset_in_segment: 0,
+                value: BytesMut::from(&[1u8; 10][..]),
+            }),
+        );
+        assert_eq!(reader.meta.prefetch_limit.available_permits(), max_prefetch - 10);
+
+        // the permits are given back once the segment is released.
+        cf.get_runtime()
+            .block_on(reader.release_segment(slice))
+            .expect("release segment");
+        assert!(reader.meta.buffered_reads.is_empty());
+        assert_eq!(reader.meta.prefetch_limit.available_permits(), max_prefetch);
     }
 
     #[test]
-    fn test_return_slice_at_offset() {
-        const NUM_EVENTS: usize = 2;
+    fn test_slice_not_returned() {
         let (tx, rx) = mpsc::channel(1);
-        let (stop_tx, stop_rx) = oneshot::channel();
-        tracing_subscriber::fmt().with_max_level(Level::TRACE).finish();
-        let cf = ClientFactory::new(
-            ClientConfigBuilder::default()
-                .controller_uri(MOCK_CONTROLLER_URI)
-                .build()
-                .unwrap(),
+        let cf = create_client_factory();
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_is_end_of_stream().returning(|| Ok(false));
+        rg_mock
+            .expect_release_segment()
+            .with(
+                predicate::always(),
+                predicate::eq(segment.clone()),
+                predicate::eq(Offset::new(0)),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let mut reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
+            tx,
+            rx,
+            create_slice_map(vec![create_segment_slice(0)]),
+            HashMap::new(),
         );
+        reader.config = ReaderConfigBuilder::default().set_acquire_timeout(100).build();
+        let slice_meta = reader.meta.slices.remove(&segment).expect("slice meta");
+        let mut slice = reader.dish_out_slice(slice_meta);
+
+        // the slice is dropped without being returned to the reader.
+        drop(slice.slice_return_tx.take());
+        assert!(cf
+            .get_runtime()
+            .block_on(reader.meta.remove_segment(segment.clone()))
+            .is_none());
+        assert_eq!(reader.meta.unreturned.get(&segment), Some(&0));
+
+        // the segment is released from the reader at the offset the slice was handed out at.
+        assert!(cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .is_none());
+        assert!(reader.meta.unreturned.is_empty());
+        assert!(reader.meta.committed_offsets().is_empty());
+    }
 
-        // simulate data being received from Segment store.
-        let _guard = cf.get_runtime().enter();
-        tokio::spawn(generate_constant_size_events(
-            tx.clone(),
-            20,
-            NUM_EVENTS,
-            0,
-            false,
-            stop_rx,
+    #[test]
+    fn test_init_reader_failure() {
+        let cf = create_client_factory();
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock
+            .expect_get_generation()
+            .times(READER_GROUP_STATE_RETRIES as usize + 1)
+            .returning(|| {
+                Err(ReaderGroupStateError::SyncError {
+                    error_msg: "fetch generation".to_string(),
+                    source: SynchronizerError::SyncUpdateError {
+                        error_msg: "table segment unavailable".to_string(),
+                    },
+                })
+            });
+
+        // the error is returned once the reader group state cannot be read after a few retries.
+        let res = cf.get_runtime().block_on(EventReader::init_reader(
+            "r1".to_string(),
+            Arc::new(Mutex::new(rg_mock)),
+            cf.clone(),
+            ReaderConfig::default(),
         ));
-        let mut stop_reading_map: HashMap<ScopedSegment, oneshot::Sender<()>> = HashMap::new();
-        stop_reading_map.insert(ScopedSegment::from("scope/test/0.#epoch.0"), stop_tx);
+        assert!(matches!(res, Err(ReaderGroupStateError::SyncError { .. })));
+    }
 
-        // simulate initialization of a Reader
-        let init_segments = vec![create_segment_slice(0), create_segment_slice(1)];
-        let mut rg_mock: ReaderGroupState = ReaderGroupState::default();
-        rg_mock
-            .expect_compute_segments_to_acquire_or_release()
-            .return_const(0 as isize);
-        // create a new Event Reader with the segment slice data.
+    #[test]
+    fn test_pause_and_resume_segment() {
+        let (tx, rx) = mpsc::channel(2);
+        let stale_tx = tx.clone();
+        let cf = create_client_factory();
+        let segment = ScopedSegment::from("scope/pause/0.#epoch.0");
+        cf.get_runtime().block_on(create_stream(&cf, "scope", "pause"));
+        let mut writer = cf.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 100]).expect("write to segment");
+        writer.flush().expect("flush segment");
+
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let mut stop_reading_map = HashMap::new();
+        stop_reading_map.insert(segment.clone(), stop_tx);
+        let mut slice = create_segment_slice(0);
+        slice.meta.scoped_segment = segment.to_string();
+        slice.meta.start_offset = 10;
+        slice.meta.read_offset = 10;
+        let mut rg_mock = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
         let mut reader = EventReader::init_event_reader(
             Arc::new(Mutex::new(rg_mock)),
             Reader::from("r1".to_string()),
             cf.clone(),
-            tx.clone(),
+            tx,
             rx,
-            create_slice_map(init_segments),
+            create_slice_map(vec![slice]),
             stop_reading_map,
         );
+        reader.config = ReaderConfigBuilder::default().set_acquire_timeout(100).build();
+
+        // the read task is stopped while the segment is kept by the reader.
+        assert!(reader.pause(&segment));
+        assert!(stop_rx.try_recv().is_ok());
+        assert!(reader.meta.slices.contains_key(&segment));
+        assert!(cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .is_none());
+        assert!(!reader.resume(&ScopedSegment::from("scope/pause/1.#epoch.0")));
+
+        // a read issued before the pause arrives late and is dropped.
+        let stale_epoch = 0;
+        assert_ne!(reader.get_read_epoch(&segment), stale_epoch);
+        stale_tx
+            .try_send((
+                stale_epoch,
+                Ok(SegmentDataBuffer {
+                    segment: segment.to_string(),
+                    offset_in_segment: 10,
+                    value: BytesMut::from(&[2u8; 5][..]),
+                }),
+            ))
+            .expect("send stale read");
+
+        // reading continues from the same offset.
+        assert!(reader.resume(&segment));
+        assert!(reader.meta.slice_stop_reading.contains_key(&segment));
+        let slice = cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .expect("segment slice");
+        assert_eq!(slice.meta.segment_data.offset_in_segment, 10);
+        assert_eq!(slice.meta.segment_data.value.len(), 90);
+        drop(slice);
+        assert!(reader.pause(&segment));
+    }
 
-        // acquire a segment
-        let mut slice = cf.get_runtime().block_on(reader.acquire_segment()).unwrap();
-
-        // read an event.
-        let event = slice.next().unwrap();
-        assert_eq!(event.value.len(), 1);
-        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-        assert_eq!(event.offset_in_segment, 0); // first event.
-
-        let result = slice.next();
-        assert!(result.is_some());
-        let event = result.unwrap();
-        assert_eq!(event.value.len(), 1);
-        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-        assert_eq!(event.offset_in_segment, 9); // second event.
+    #[test]
+    fn test_segment_truncated() {
+        // the reader skips ahead to the current head of the segment.
+        let (cf, mut reader) = create_reader_with_truncated_segment(TruncationPolicy::SkipToHead);
+        assert!(cf
+            .get_runtime()
+            .block_on(reader.acquire_segment())
+            .expect("acquire segment")
+            .is_none());
+        assert_eq!(reader.get_truncated_bytes_skipped(), 40);
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+        assert_eq!(reader.meta.slices.get(&segment).unwrap().read_offset, 50);
+
+        // the reader returns an error.
+        let (cf, mut reader) = create_reader_with_truncated_segment(TruncationPolicy::Fail);
+        let res = cf.get_runtime().block_on(reader.acquire_segment());
+        match res {
+            Err(EventReaderError::SegmentTruncated {
+                segment,
+                offset,
+                head,
+            }) => {
+                assert_eq!(segment, "scope/test/0.#epoch.0");
+                assert_eq!(offset, 10);
+                assert_eq!(head, 50);
+            }
+            _ => panic!("segment truncated error expected"),
+        }
+        assert_eq!(reader.get_truncated_bytes_skipped(), 0);
+        assert!(!reader.meta.slices.contains_key(&segment));
+        // the segment stays assigned to the reader and keeps failing it.
+        assert!(matches!(
+            cf.get_runtime().block_on(reader.acquire_segment()),
+            Err(EventReaderError::SegmentTruncated {
+                offset: 10,
+                head: 50,
+                ..
+            })
+        ));
+        assert_eq!(
+            reader.meta.committed_offsets().get(&segment),
+            Some(&Offset::new(10))
+        );
+    }
 
-        // release the segment slice.
-        cf.get_runtime().block_on(reader.release_segment_at(slice, 0));
+    // Create a reader which reads segment 0 at offset 10, the segment is truncated at offset 50.
+    fn create_reader_with_truncated_segment(policy: TruncationPolicy) -> (ClientFactory, EventReader) {
+        let (tx, rx) = mpsc::channel(1);
+        let cf = create_client_factory();
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+        cf.get_runtime().block_on(create_stream(&cf, "scope", "test"));
+        let mut writer = cf.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 100]).expect("write to segment");
+        writer.flush().expect("flush segment");
+        cf.get_runtime().block_on(async {
+            cf.create_segment_metadata_client(segment.clone())
+                .await
+                .truncate_segment(50)
+                .await
+                .expect("truncate segment");
+        });
 
-        // simulate a segment read at offset 0.
-        let (_stop_tx, stop_rx) = oneshot::channel();
-        tokio::spawn(generate_constant_size_events(
+        let mut slice0 = create_segment_slice(0);
+        slice0.meta.start_offset = 10;
+        slice0.meta.read_offset = 10;
+        let mut rg_mock: ReaderGroupState = create_rg_mock();
+        rg_mock.expect_get_generation().returning(|| Ok(0u64));
+        rg_mock
+            .expect_get_truncation_policy()
+            .returning(move || Ok(policy));
+        let reader = EventReader::init_event_reader(
+            Arc::new(Mutex::new(rg_mock)),
+            Reader::from("r1".to_string()),
+            cf.clone(),
             tx.clone(),
-            20,
-            NUM_EVENTS,
-            0,
-            false,
-            stop_rx,
-        ));
-
-        // acquire the next segment
-        let mut slice = cf.get_runtime().block_on(reader.acquire_segment()).unwrap();
-        // Verify a partial event being present. This implies
-        let event = slice.next().unwrap();
-        assert_eq!(event.value.len(), 1);
-        assert!(is_all_same(event.value.as_slice()), "Event has been corrupted");
-        assert_eq!(event.offset_in_segment, 0); // first event.
+            rx,
+            create_slice_map(vec![slice0]),
+            HashMap::new(),
+        );
+        let error = ReaderError::SegmentIsTruncated {
+            segment: segment.to_string(),
+            can_retry: false,
+            operation: "read segment".to_string(),
+            error_msg: "segment is truncated".to_string(),
+        };
+        cf.get_runtime()
+            .block_on(tx.send((0, Err((error, 10)))))
+            .expect("send truncation error");
+        (cf, reader)
     }
 
     fn read_n_events(slice: &mut SegmentSlice, events_to_read: usize) {
@@ -1235,10 +3203,11 @@ mod tests {
 
     // Generate events to simulate Pravega SegmentReadCommand.
     async fn generate_constant_size_events(
-        tx: Sender<SegmentReadResult>,
+        tx: Sender<SegmentReadResultWithEpoch>,
         buf_size: usize,
         num_events: usize,
         segment_id: usize,
+        read_epoch: u64,
         should_delay: bool,
         mut stop_generation: oneshot::Receiver<()>,
     ) {
@@ -1260,11 +3229,14 @@ mod tests {
                         if should_delay {
                             sleep(Duration::from_millis(100)).await;
                         }
-                        tx.send(Ok(SegmentDataBuffer {
-                            segment: ScopedSegment::from(segment_name.as_str()).to_string(),
-                            offset_in_segment: offset,
-                            value: buf,
-                        }))
+                        tx.send((
+                            read_epoch,
+                            Ok(SegmentDataBuffer {
+                                segment: ScopedSegment::from(segment_name.as_str()).to_string(),
+                                offset_in_segment: offset,
+                                value: buf,
+                            }),
+                        ))
                         .await
                         .unwrap();
                         offset += buf_size as i64;
@@ -1278,18 +3250,21 @@ mod tests {
             }
         }
         // send the last event.
-        tx.send(Ok(SegmentDataBuffer {
-            segment: ScopedSegment::from(segment_name.as_str()).to_string(),
-            offset_in_segment: offset,
-            value: buf,
-        }))
+        tx.send((
+            read_epoch,
+            Ok(SegmentDataBuffer {
+                segment: ScopedSegment::from(segment_name.as_str()).to_string(),
+                offset_in_segment: offset,
+                value: buf,
+            }),
+        ))
         .await
         .unwrap();
     }
 
     // Generate events to simulate Pravega SegmentReadCommand.
     async fn generate_variable_size_events(
-        tx: Sender<SegmentReadResult>,
+        tx: Sender<SegmentReadResultWithEpoch>,
         buf_size: usize,
         num_events: usize,
         segment_id: usize,
@@ -1311,11 +3286,14 @@ mod tests {
                         if should_delay {
                             sleep(Duration::from_millis(100)).await;
                         }
-                        tx.send(Ok(SegmentDataBuffer {
-                            segment: ScopedSegment::from(segment_name.as_str()).to_string(),
-                            offset_in_segment: offset,
-                            value: buf,
-                        }))
+                        tx.send((
+                            0,
+                            Ok(SegmentDataBuffer {
+                                segment: ScopedSegment::from(segment_name.as_str()).to_string(),
+                                offset_in_segment: offset,
+                                value: buf,
+                            }),
+                        ))
                         .await
                         .unwrap();
                         offset += buf_size as i64;
@@ -1329,11 +3307,14 @@ mod tests {
             }
         }
         // send the last event.
-        tx.send(Ok(SegmentDataBuffer {
-            segment: ScopedSegment::from(segment_name.as_str()).to_string(),
-            offset_in_segment: offset,
-            value: buf,
-        }))
+        tx.send((
+            0,
+            Ok(SegmentDataBuffer {
+                segment: ScopedSegment::from(segment_name.as_str()).to_string(),
+                offset_in_segment: offset,
+                value: buf,
+            }),
+        ))
         .await
         .unwrap();
     }
@@ -1350,6 +3331,25 @@ mod tests {
         buf
     }
 
+    // create a client factory which mocks both the controller and the segment store.
+    fn create_client_factory() -> ClientFactory {
+        ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    // create a mock of the reader group state which lets the dropped readers go offline.
+    fn create_rg_mock() -> ReaderGroupState {
+        let mut rg_mock = ReaderGroupState::default();
+        rg_mock.expect_remove_reader().returning(|_, _| Ok(()));
+        rg_mock
+    }
+
     // create a segment slice object without spawning a background task for testing
     fn create_segment_slice(segment_id: i64) -> SegmentSlice {
         let mut segment_name = "scope/test/".to_owned();
@@ -1364,8 +3364,10 @@ mod tests {
                 end_offset: i64::MAX,
                 segment_data: SegmentDataBuffer::empty(),
                 partial_data_present: false,
+                parsed_segment: None,
             },
             slice_return_tx: None,
+            ack_tracker: None,
         };
         segment_slice
     }
@@ -10,12 +10,18 @@
 
 use crate::client_factory::ClientFactory;
 use crate::event_reader::EventReader;
-use crate::reader_group::reader_group_state::Offset;
-use crate::reader_group_config::ReaderGroupConfig;
-use pravega_client_shared::{Reader, Scope, ScopedSegment, ScopedStream};
-use std::collections::HashMap;
+use crate::metric::ClientMetrics;
+use crate::reader_group::reader_group_state::{Offset, ReaderGroupStateError};
+use crate::reader_group_config::{ReaderConfig, ReaderGroupConfig};
+use crate::stream::stream_cut::{stream_cut_at_time, StreamCutV1, StreamCutVersioned};
+use pravega_client_shared::{Reader, Scope, ScopedSegment, ScopedStream, Segment, StreamCut};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
 cfg_if::cfg_if! {
     if #[cfg(test)] {
         use crate::reader_group::reader_group_state::MockReaderGroupState as ReaderGroupState;
@@ -52,21 +58,116 @@ cfg_if::cfg_if! {
 ///         stream: Stream::from("stream".to_string()),
 ///     };
 ///     // Create a reader group to read data from the Pravega stream.
-///     let rg = client_factory.create_reader_group(scope, "rg".to_string(), stream).await;
+///     let rg = client_factory
+///         .create_reader_group(scope, "rg".to_string(), stream)
+///         .await
+///         .expect("create reader group");
 ///     // Create a reader under the reader group.
-///     let mut reader1 = rg.create_reader("r1".to_string()).await;
-///     let mut reader2 = rg.create_reader("r2".to_string()).await;
+///     let mut reader1 = rg.create_reader("r1".to_string()).await.expect("create reader");
+///     let mut reader2 = rg.create_reader("r2".to_string()).await.expect("create reader");
 ///     // EventReader APIs can be used to read events.
 /// }
 /// ```
+///
 #[derive(new)]
 pub struct ReaderGroup {
     name: String,
     config: ReaderGroupConfig,
     pub state: Arc<Mutex<ReaderGroupState>>,
     client_factory: ClientFactory,
+    // the sender of the notification poller, it is shared by all the subscribers.
+    #[new(default)]
+    notifier: Notifier,
 }
 
+type Notifier = Arc<std::sync::Mutex<Option<broadcast::Sender<ReaderGroupNotification>>>>;
+
+///
+/// The number of bytes that are yet to be read by a reader group.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnreadBytes {
+    /// The unread bytes in the segments assigned to each online reader.
+    pub readers: HashMap<Reader, u64>,
+    /// The unread bytes of the whole reader group, including unassigned and future segments.
+    pub total: u64,
+}
+
+///
+/// Notifications emitted by the reader group, see [`ReaderGroup::notifications`].
+///
+/// [`ReaderGroup::notifications`]: ReaderGroup::notifications
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReaderGroupNotification {
+    /// The number of segments of the reader group has changed, e.g. after a stream was scaled.
+    SegmentCountChanged { segments: usize, readers: usize },
+    /// Readers have joined or left the reader group.
+    ReadersChanged { joined: Vec<Reader>, left: Vec<Reader> },
+    /// All the streams of the reader group are sealed and have been read completely.
+    EndOfData,
+}
+
+///
+/// A snapshot of the reader group state, see [`ReaderGroup::describe`].
+///
+/// [`ReaderGroup::describe`]: ReaderGroup::describe
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReaderGroupInfo {
+    /// The name of the reader group.
+    pub name: String,
+    /// The configuration of the reader group.
+    pub config: ReaderGroupConfig,
+    /// The online readers along with the segments assigned to them.
+    pub readers: Vec<ReaderInfo>,
+    /// The segments that are not assigned to any reader.
+    pub unassigned_segments: Vec<SegmentInfo>,
+    /// The successor segments that will be readable once their predecessors are completed.
+    pub future_segments: Vec<FutureSegmentInfo>,
+}
+
+///
+/// An online reader of a reader group and the segments assigned to it.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReaderInfo {
+    pub reader: Reader,
+    pub segments: Vec<SegmentInfo>,
+}
+
+///
+/// A segment of a reader group and the offset last published for it.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SegmentInfo {
+    pub segment: ScopedSegment,
+    pub offset: i64,
+}
+
+///
+/// A future segment and the predecessors that have yet to be completed.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FutureSegmentInfo {
+    pub segment: ScopedSegment,
+    pub predecessors: Vec<Segment>,
+}
+
+fn to_segment_infos(segments: HashMap<ScopedSegment, Offset>) -> Vec<SegmentInfo> {
+    let mut infos: Vec<SegmentInfo> = segments
+        .into_iter()
+        .map(|(segment, offset)| SegmentInfo {
+            segment,
+            offset: offset.read,
+        })
+        .collect();
+    infos.sort_by_key(|info| info.segment.to_string());
+    infos
+}
+
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 100;
+
 impl ReaderGroup {
     // This ensures the mock reader group state object is used for unit tests.
     cfg_if::cfg_if! {
@@ -101,28 +202,10 @@ impl ReaderGroup {
     pub async fn create(
         scope: Scope,
         name: String,
-        rg_config: ReaderGroupConfig,
+        mut rg_config: ReaderGroupConfig,
         client_factory: ClientFactory,
-    ) -> ReaderGroup {
-        let streams: Vec<ScopedStream> = rg_config.get_streams();
-        let mut init_segments: HashMap<ScopedSegment, Offset> = HashMap::new();
-        for stream in streams {
-            let segments = client_factory
-                .get_controller_client()
-                .get_head_segments(&stream)
-                .await
-                .expect("Error while fetching stream's starting segments to read from ");
-            init_segments.extend(segments.iter().map(|(seg, off)| {
-                (
-                    ScopedSegment {
-                        scope: stream.scope.clone(),
-                        stream: stream.stream.clone(),
-                        segment: seg.clone(),
-                    },
-                    Offset::new(*off),
-                )
-            }));
-        }
+    ) -> Result<ReaderGroup, ReaderGroupStateError> {
+        let init_segments = ReaderGroup::get_init_segments(&mut rg_config, &client_factory).await?;
         let rg_state = ReaderGroup::create_rg_state(
             scope,
             name.clone(),
@@ -131,34 +214,502 @@ impl ReaderGroup {
             init_segments,
         )
         .await;
-        ReaderGroup {
+        Ok(ReaderGroup {
             name: name.clone(),
             config: rg_config.clone(),
             state: Arc::new(Mutex::new(rg_state)),
             client_factory,
+            notifier: Default::default(),
+        })
+    }
+
+    ///
+    /// Generate the current stream cuts of the streams read by the reader group. The stream cuts
+    /// are based on the positions last published by the readers of the reader group, readers can
+    /// publish their latest positions using [`EventReader::publish_positions`].
+    ///
+    /// [`EventReader::publish_positions`]: EventReader::publish_positions
+    ///
+    pub async fn generate_stream_cuts(
+        &self,
+    ) -> Result<HashMap<ScopedStream, StreamCut>, ReaderGroupStateError> {
+        self.state.lock().await.get_stream_cuts().await
+    }
+
+    ///
+    /// Describe the current state of the reader group: the online readers with the segments and
+    /// offsets assigned to each of them, the unassigned and future segments and the configuration.
+    /// The description is built from a single snapshot of the reader group state, so the updates
+    /// made by other instances of the reader group are included. The lists are sorted so that the
+    /// output is stable.
+    ///
+    pub async fn describe(&self) -> Result<ReaderGroupInfo, ReaderGroupStateError> {
+        let snapshot = self.state.lock().await.get_snapshot().await?;
+        let mut readers: Vec<ReaderInfo> = snapshot
+            .assigned
+            .into_iter()
+            .map(|(reader, segments)| ReaderInfo {
+                reader,
+                segments: to_segment_infos(segments),
+            })
+            .collect();
+        readers.sort_by_key(|info| info.reader.to_string());
+        let mut future_segments: Vec<FutureSegmentInfo> = snapshot
+            .future
+            .into_iter()
+            .map(|(segment, predecessors)| {
+                let mut predecessors: Vec<Segment> = predecessors.into_iter().collect();
+                predecessors.sort_by_key(|s| s.number);
+                FutureSegmentInfo {
+                    segment,
+                    predecessors,
+                }
+            })
+            .collect();
+        future_segments.sort_by_key(|info| info.segment.to_string());
+        Ok(ReaderGroupInfo {
+            name: self.name.clone(),
+            config: ReaderGroupConfig {
+                config: snapshot.config,
+            },
+            readers,
+            unassigned_segments: to_segment_infos(snapshot.unassigned),
+            future_segments,
+        })
+    }
+
+    ///
+    /// Compute the number of bytes the reader group has yet to read. The unread bytes of a segment
+    /// is the difference between the current length of the segment and the offset last published
+    /// for it in a single snapshot of the reader group state. The unread bytes of each reader
+    /// and the total are reported as gauges.
+    ///
+    pub async fn unread_bytes(&self) -> Result<UnreadBytes, ReaderGroupStateError> {
+        let snapshot = self.state.lock().await.get_snapshot().await?;
+        let mut readers: HashMap<Reader, u64> = HashMap::new();
+        for (reader, segments) in snapshot.assigned {
+            let mut unread = 0;
+            for (segment, offset) in segments {
+                unread += self.unread_bytes_in_segment(segment, offset.read).await?;
+            }
+            readers.insert(reader, unread);
+        }
+        let mut total: u64 = readers.values().sum();
+        for (segment, offset) in snapshot.unassigned {
+            total += self.unread_bytes_in_segment(segment, offset.read).await?;
+        }
+        for segment in snapshot.future.keys() {
+            total += self.unread_bytes_in_segment(segment.clone(), 0).await?;
+        }
+
+        for (reader, unread) in &readers {
+            update!(
+                ClientMetrics::ReaderUnreadBytes,
+                *unread,
+                "Reader Group" => self.name.clone(),
+                "Reader" => reader.to_string()
+            );
         }
+        update!(ClientMetrics::ReaderGroupUnreadBytes, total, "Reader Group" => self.name.clone());
+        Ok(UnreadBytes { readers, total })
+    }
+
+    // Fetch the number of bytes after the given offset in a segment.
+    async fn unread_bytes_in_segment(
+        &self,
+        segment: ScopedSegment,
+        offset: i64,
+    ) -> Result<u64, ReaderGroupStateError> {
+        let length = self
+            .client_factory
+            .create_segment_metadata_client(segment.clone())
+            .await
+            .fetch_current_segment_length()
+            .await
+            .map_err(|e| ReaderGroupStateError::SegmentMetadataError {
+                error_msg: format!("fetch length of segment {:?}", segment),
+                source: e,
+            })?;
+        Ok(if length > offset {
+            (length - offset) as u64
+        } else {
+            0
+        })
     }
 
     ///
-    /// Get the reader name.
+    /// Delete the reader group. All the readers of the reader group are marked offline and the
+    /// table segment which stores the reader group state is deleted.
     ///
-    pub fn get_name(&self) -> String {
-        self.name.clone()
+    pub async fn delete(self) -> Result<(), ReaderGroupStateError> {
+        info!("Deleting reader group {:?}", self.name);
+        self.state.lock().await.delete().await
     }
 
     ///
-    /// Create a new EventReader under the ReaderGroup. This method panics if the reader is
-    /// already part of the reader group.
+    /// Return true if all the streams of the reader group are sealed and every segment has been
+    /// read completely.
     ///
-    pub async fn create_reader(&self, reader_id: String) -> EventReader {
-        let r: Reader = Reader::from(reader_id.clone());
+    pub async fn is_end_of_stream(&self) -> Result<bool, ReaderGroupStateError> {
+        self.state.lock().await.is_end_of_stream().await
+    }
+
+    ///
+    /// Wait until all the streams of the reader group are sealed and every segment has been read
+    /// completely. The reader group state is checked once every group refresh time.
+    ///
+    pub async fn wait_for_end_of_stream(&self) -> Result<(), ReaderGroupStateError> {
+        let refresh_time = Duration::from_millis(self.config.config.get_group_refresh_time_millis());
+        while !self.is_end_of_stream().await? {
+            sleep(refresh_time).await;
+        }
+        info!("Reader group {:?} has reached the end of stream", self.name);
+        Ok(())
+    }
+
+    ///
+    /// Subscribe to the notifications of the reader group. A background task polls the reader
+    /// group state once every group refresh time and emits a notification when the number of
+    /// segments changes, when readers join or leave and when the end of data is reached.
+    /// The task is started by the first subscriber and is shared by all the subscribers of the
+    /// reader group, its first poll reports the current number of segments and readers. The task
+    /// stops once all the receivers are dropped. Polls which fail to read the reader group state
+    /// are skipped.
+    ///
+    pub fn notifications(&self) -> broadcast::Receiver<ReaderGroupNotification> {
+        let mut notifier = self.notifier.lock().expect("lock notifier");
+        if let Some(tx) = notifier.as_ref() {
+            return tx.subscribe();
+        }
+        let (tx, rx) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        *notifier = Some(tx.clone());
+        let refresh_time = Duration::from_millis(self.config.config.get_group_refresh_time_millis());
+        self.client_factory
+            .get_runtime()
+            .spawn(ReaderGroup::poll_notifications(
+                self.state.clone(),
+                self.notifier.clone(),
+                tx,
+                refresh_time,
+            ));
+        rx
+    }
+
+    async fn poll_notifications(
+        state: Arc<Mutex<ReaderGroupState>>,
+        notifier: Notifier,
+        tx: broadcast::Sender<ReaderGroupNotification>,
+        refresh_time: Duration,
+    ) {
+        let mut segment_count = None;
+        let mut readers: HashSet<Reader> = HashSet::new();
+        let mut end_of_data = false;
+        while !ReaderGroup::stop_notifications(&notifier, &tx) {
+            let polled = state.lock().await.get_snapshot().await;
+            let snapshot = match polled {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    warn!("failed to poll the reader group state for notifications: {}", e);
+                    sleep(refresh_time).await;
+                    continue;
+                }
+            };
+            let mut notifications = vec![];
+
+            let current_readers: HashSet<Reader> = snapshot.assigned.keys().cloned().collect();
+            let joined: Vec<Reader> = current_readers.difference(&readers).cloned().collect();
+            let left: Vec<Reader> = readers.difference(&current_readers).cloned().collect();
+            if !joined.is_empty() || !left.is_empty() {
+                notifications.push(ReaderGroupNotification::ReadersChanged { joined, left });
+            }
+
+            let segments =
+                snapshot.assigned.values().map(|s| s.len()).sum::<usize>() + snapshot.unassigned.len();
+            if segment_count != Some(segments) {
+                notifications.push(ReaderGroupNotification::SegmentCountChanged {
+                    segments,
+                    readers: current_readers.len(),
+                });
+            }
+
+            if snapshot.end_of_stream && !end_of_data {
+                notifications.push(ReaderGroupNotification::EndOfData);
+            }
+
+            for notification in notifications {
+                debug!("reader group notification {:?}", notification);
+                // the receivers dropped in the meantime are handled by the next iteration.
+                let _ = tx.send(notification);
+            }
+            segment_count = Some(segments);
+            readers = current_readers;
+            end_of_data = snapshot.end_of_stream;
+            sleep(refresh_time).await;
+        }
+    }
+
+    // Return true once all the receivers are dropped. The sender is removed from the reader group
+    // while holding its lock, so that a new subscriber starts a new task instead of subscribing
+    // to this one.
+    fn stop_notifications(notifier: &Notifier, tx: &broadcast::Sender<ReaderGroupNotification>) -> bool {
+        let mut notifier = notifier.lock().expect("lock notifier");
+        if tx.receiver_count() > 0 {
+            return false;
+        }
+        *notifier = None;
+        true
+    }
+
+    ///
+    /// Mark the given reader offline. The segments owned by the reader are returned to the
+    /// unassigned list starting from the provided positions, segments not present in
+    /// last_position start from the last position published by the reader.
+    ///
+    /// This is used to reclaim the segments of a reader which is known to be dead without
+    /// waiting for its lease to expire.
+    ///
+    pub async fn reader_offline(
+        &self,
+        reader_id: String,
+        last_position: HashMap<ScopedSegment, Offset>,
+    ) -> Result<(), ReaderGroupStateError> {
+        info!(
+            "Putting reader {} of reader group {:?} offline",
+            reader_id, self.name
+        );
         self.state
             .lock()
             .await
-            .add_reader(&r)
+            .remove_reader(&Reader::from(reader_id), last_position)
             .await
-            .expect("Error while creating the reader");
-        EventReader::init_reader(reader_id, self.state.clone(), self.client_factory.clone()).await
+    }
+
+    ///
+    /// Reset the reader group to the provided ReaderGroupConfig. The reader group state is
+    /// atomically rewritten so that all the segments are read again starting from the stream cuts
+    /// of the new config. Online readers stay part of the reader group, they drop their current
+    /// segments once they observe the reset and acquire segments of the new config afterwards.
+    ///
+    pub async fn reset(&mut self, mut rg_config: ReaderGroupConfig) -> Result<(), ReaderGroupStateError> {
+        let init_segments = ReaderGroup::get_init_segments(&mut rg_config, &self.client_factory).await?;
+        self.state
+            .lock()
+            .await
+            .reset(rg_config.config.clone(), init_segments)
+            .await?;
+        self.config = rg_config;
+        Ok(())
+    }
+
+    ///
+    /// Add a stream to the reader group, the readers start reading it from the given stream cut.
+    /// A stream cut without segments means the head of the stream. The config of the reader group
+    /// and the segments of the stream are updated in the reader group state at once, online
+    /// readers acquire the segments of the stream at their next rebalance.
+    ///
+    pub async fn add_stream(
+        &mut self,
+        stream: ScopedStream,
+        stream_cut: StreamCut,
+    ) -> Result<(), ReaderGroupStateError> {
+        let cut = StreamCutVersioned::from(stream_cut);
+        let segments = ReaderGroup::get_stream_segments(&stream, cut.clone(), &self.client_factory).await?;
+        let mut state = self.state.lock().await;
+        state.add_stream(&stream, cut, segments).await?;
+        self.config.config = state.get_config().await?;
+        Ok(())
+    }
+
+    ///
+    /// Remove a stream from the reader group. The config of the reader group and the segments of the
+    /// stream are removed from the reader group state at once. Online readers drop the segments of
+    /// the removed stream at their next rebalance and keep reading the other streams. Removing a
+    /// stream which is not read by the reader group is a no-op.
+    ///
+    pub async fn remove_stream(&mut self, stream: &ScopedStream) -> Result<(), ReaderGroupStateError> {
+        let mut state = self.state.lock().await;
+        state.remove_stream(stream).await?;
+        self.config.config = state.get_config().await?;
+        Ok(())
+    }
+
+    ///
+    /// Build the stream cuts of all the streams read by the reader group at the given wall clock
+    /// time, see [`stream_cut_at_time`].
+    ///
+    /// [`stream_cut_at_time`]: crate::stream::stream_cut::stream_cut_at_time
+    ///
+    pub async fn get_stream_cuts_at_time(
+        &self,
+        time: SystemTime,
+    ) -> Result<HashMap<ScopedStream, StreamCut>, ReaderGroupStateError> {
+        let mut stream_cuts = HashMap::new();
+        for stream in self.config.get_streams() {
+            let stream_cut = stream_cut_at_time(self.client_factory.get_controller_client(), &stream, time)
+                .await
+                .map_err(|e| ReaderGroupStateError::ControllerRequestError {
+                    error_msg: format!("get segments of stream {:?} at time {:?}", stream, time),
+                    err: e,
+                })?;
+            stream_cuts.insert(stream, stream_cut);
+        }
+        Ok(stream_cuts)
+    }
+
+    ///
+    /// Reset the reader group so that the readers start reading all the streams from the events
+    /// written at the given wall clock time. See [`ReaderGroup::reset`].
+    ///
+    /// [`ReaderGroup::reset`]: ReaderGroup::reset
+    ///
+    pub async fn reset_to_time(&mut self, time: SystemTime) -> Result<(), ReaderGroupStateError> {
+        let stream_cuts = self.get_stream_cuts_at_time(time).await?;
+        self.reset_to_stream_cuts(stream_cuts).await
+    }
+
+    ///
+    /// Reset the reader group so that the readers start reading the given streams from the given
+    /// stream cuts, the other streams are read again from their starting stream cuts.
+    /// See [`ReaderGroup::reset`].
+    ///
+    /// [`ReaderGroup::reset`]: ReaderGroup::reset
+    ///
+    pub async fn reset_to_stream_cuts(
+        &mut self,
+        stream_cuts: HashMap<ScopedStream, StreamCut>,
+    ) -> Result<(), ReaderGroupStateError> {
+        let mut rg_config = self.config.clone();
+        for (stream, stream_cut) in stream_cuts {
+            rg_config
+                .config
+                .add_stream(stream, StreamCutVersioned::from(stream_cut));
+        }
+        self.reset(rg_config).await
+    }
+
+    // Fetch the segments and offsets the readers should start reading from for the given config.
+    // The stream cuts pointing to the tail of the streams are replaced by the fetched tail offsets,
+    // so that the stored config keeps the position the readers started from.
+    async fn get_init_segments(
+        rg_config: &mut ReaderGroupConfig,
+        client_factory: &ClientFactory,
+    ) -> Result<HashMap<ScopedSegment, Offset>, ReaderGroupStateError> {
+        let mut init_segments: HashMap<ScopedSegment, Offset> = HashMap::new();
+        for (stream, cut) in rg_config.get_starting_stream_cuts() {
+            let segments = ReaderGroup::get_stream_segments(&stream, cut.clone(), client_factory).await?;
+            if cut == StreamCutVersioned::Tail {
+                let positions = segments
+                    .iter()
+                    .map(|(segment, offset)| (segment.clone(), offset.read))
+                    .collect();
+                debug!("stream {:?} starts at its tail {:?}", stream, positions);
+                rg_config.config.set_starting_stream_cut(
+                    stream.clone(),
+                    StreamCutVersioned::V1(StreamCutV1::new(stream, positions)),
+                );
+            }
+            init_segments.extend(segments);
+        }
+        Ok(init_segments)
+    }
+
+    // Fetch the segments and offsets of a stream the readers should start reading from.
+    async fn get_stream_segments(
+        stream: &ScopedStream,
+        cut: StreamCutVersioned,
+        client_factory: &ClientFactory,
+    ) -> Result<HashMap<ScopedSegment, Offset>, ReaderGroupStateError> {
+        match cut {
+            StreamCutVersioned::V1(cut) => Ok(cut
+                .get_positions()
+                .into_iter()
+                .map(|(seg, off)| (seg, Offset::new(off)))
+                .collect()),
+            StreamCutVersioned::UNBOUNDED => {
+                let segments = client_factory
+                    .get_controller_client()
+                    .get_head_segments(stream)
+                    .await
+                    .map_err(|e| ReaderGroupStateError::ControllerRequestError {
+                        error_msg: format!("get head segments of stream {:?}", stream),
+                        err: e,
+                    })?;
+                Ok(segments
+                    .iter()
+                    .map(|(seg, off)| {
+                        (
+                            ScopedSegment {
+                                scope: stream.scope.clone(),
+                                stream: stream.stream.clone(),
+                                segment: seg.clone(),
+                            },
+                            Offset::new(*off),
+                        )
+                    })
+                    .collect())
+            }
+            StreamCutVersioned::Tail => ReaderGroup::get_tail_segments(stream, client_factory).await,
+        }
+    }
+
+    // Fetch the current segments of a stream and their lengths.
+    async fn get_tail_segments(
+        stream: &ScopedStream,
+        client_factory: &ClientFactory,
+    ) -> Result<HashMap<ScopedSegment, Offset>, ReaderGroupStateError> {
+        let segments = client_factory
+            .get_controller_client()
+            .get_current_segments(stream)
+            .await
+            .map_err(|e| ReaderGroupStateError::ControllerRequestError {
+                error_msg: format!("get current segments of stream {:?}", stream),
+                err: e,
+            })?;
+        let mut tail_segments = HashMap::new();
+        for segment in segments.get_segments() {
+            let length = client_factory
+                .create_segment_metadata_client(segment.clone())
+                .await
+                .fetch_current_segment_length()
+                .await
+                .map_err(|e| ReaderGroupStateError::SegmentMetadataError {
+                    error_msg: format!("fetch the length of segment {:?}", segment),
+                    source: e,
+                })?;
+            tail_segments.insert(segment, Offset::new(length));
+        }
+        Ok(tail_segments)
+    }
+
+    ///
+    /// Get the reader name.
+    ///
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    ///
+    /// Create a new EventReader under the ReaderGroup. An error is returned if the reader is
+    /// already part of the reader group or if the reader group state cannot be read.
+    ///
+    pub async fn create_reader(&self, reader_id: String) -> Result<EventReader, ReaderGroupStateError> {
+        self.create_reader_with_config(reader_id, self.config.get_reader_config())
+            .await
+    }
+
+    ///
+    /// Create a new EventReader under the ReaderGroup which uses the given ReaderConfig instead of
+    /// the ReaderConfig of the ReaderGroupConfig. An error is returned if the reader is already
+    /// part of the reader group or if the reader group state cannot be read.
+    ///
+    pub async fn create_reader_with_config(
+        &self,
+        reader_id: String,
+        config: ReaderConfig,
+    ) -> Result<EventReader, ReaderGroupStateError> {
+        let r: Reader = Reader::from(reader_id.clone());
+        self.state.lock().await.add_reader(&r).await?;
+        EventReader::init_reader(reader_id, self.state.clone(), self.client_factory.clone(), config).await
     }
 }
 
@@ -166,22 +717,44 @@ impl ReaderGroup {
 mod tests {
 
     use super::*;
+    use crate::create_stream;
+    use crate::error::SynchronizerError;
     use crate::error::SynchronizerError::SyncUpdateError;
-    use crate::reader_group::reader_group_state::ReaderGroupStateError;
-    use crate::reader_group_config::ReaderGroupConfigBuilder;
+    use crate::reader_group::reader_group_state::ReaderGroupSnapshot;
+    use crate::reader_group_config::{ReaderGroupConfigBuilder, StartPosition};
+    use mockall::predicate;
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
     use pravega_client_config::ClientConfigBuilder;
     use pravega_client_config::MOCK_CONTROLLER_URI;
+    use pravega_client_shared::ScopedStream;
+    use std::io::Write;
 
-    // test to validate creation of an already existing reader.
-    #[test]
-    #[should_panic]
-    fn test_create_reader_error() {
+    // create a reader group backed by the given mock state. The reader group reads the stream
+    // scope/s1 and the client factory mocks both the controller and the segment store.
+    fn create_reader_group(mock: ReaderGroupState) -> ReaderGroup {
         let client_factory = ClientFactory::new(
             ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
                 .controller_uri(MOCK_CONTROLLER_URI)
                 .build()
                 .unwrap(),
         );
+        ReaderGroup {
+            name: "rg".to_string(),
+            config: ReaderGroupConfigBuilder::default()
+                .add_stream(ScopedStream::from("scope/s1"))
+                .set_group_refresh_time(10)
+                .build(),
+            state: Arc::new(Mutex::new(mock)),
+            client_factory,
+            notifier: Default::default(),
+        }
+    }
+
+    // test to validate creation of an already existing reader.
+    #[test]
+    fn test_create_reader_error() {
         let mut mock_rg_state = ReaderGroupState::default();
 
         //Configure mock.
@@ -192,16 +765,416 @@ mod tests {
             },
         });
         mock_rg_state.expect_add_reader().return_once(move |_| err);
-        let rg = ReaderGroup {
-            name: "rg".to_string(),
+        let rg = create_reader_group(mock_rg_state);
+        assert!(rg
+            .client_factory
+            .get_runtime()
+            .block_on(rg.create_reader("r1".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_delete_reader_group() {
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state.expect_delete().times(1).return_once(|| Ok(()));
+        let rg = create_reader_group(mock_rg_state);
+        let client_factory = rg.client_factory.clone();
+        client_factory
+            .get_runtime()
+            .block_on(rg.delete())
+            .expect("delete reader group");
+    }
+
+    #[test]
+    fn test_reader_offline() {
+        let mut last_position = HashMap::new();
+        last_position.insert(ScopedSegment::from("scope/s1/0.#epoch.0"), Offset::new(10));
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state
+            .expect_remove_reader()
+            .with(
+                predicate::eq(Reader::from("r1".to_string())),
+                predicate::eq(last_position.clone()),
+            )
+            .times(1)
+            .return_once(|_, _| Ok(()));
+        let rg = create_reader_group(mock_rg_state);
+        rg.client_factory
+            .get_runtime()
+            .block_on(rg.reader_offline("r1".to_string(), last_position))
+            .expect("put reader offline");
+    }
+
+    #[test]
+    fn test_wait_for_end_of_stream() {
+        let mut mock_rg_state = ReaderGroupState::default();
+        let mut seq = mockall::Sequence::new();
+        mock_rg_state
+            .expect_is_end_of_stream()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(false));
+        mock_rg_state
+            .expect_is_end_of_stream()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(true));
+        let rg = create_reader_group(mock_rg_state);
+        rg.client_factory
+            .get_runtime()
+            .block_on(rg.wait_for_end_of_stream())
+            .expect("wait for end of stream");
+    }
+
+    #[test]
+    fn test_notifications() {
+        let reader = Reader::from("r1".to_string());
+        let mut assigned = HashMap::new();
+        let mut reader_segments = HashMap::new();
+        reader_segments.insert(ScopedSegment::from("scope/s1/0.#epoch.0"), Offset::new(0));
+        assigned.insert(reader.clone(), reader_segments);
+        let mut unassigned = HashMap::new();
+        unassigned.insert(ScopedSegment::from("scope/s1/1.#epoch.0"), Offset::new(0));
+        let snapshot = ReaderGroupSnapshot {
             config: ReaderGroupConfigBuilder::default()
                 .add_stream(ScopedStream::from("scope/s1"))
-                .build(),
-            state: Arc::new(Mutex::new(mock_rg_state)),
-            client_factory: client_factory.clone(),
+                .build()
+                .config,
+            assigned,
+            unassigned,
+            future: HashMap::new(),
+            end_of_stream: true,
         };
+        let mut mock_rg_state = ReaderGroupState::default();
+        // the first poll fails to read the reader group state and is skipped.
+        mock_rg_state.expect_get_snapshot().times(1).returning(|| {
+            Err(ReaderGroupStateError::SyncError {
+                error_msg: "get reader group snapshot".to_string(),
+                source: SynchronizerError::SyncUpdateError {
+                    error_msg: "table segment unavailable".to_string(),
+                },
+            })
+        });
+        mock_rg_state
+            .expect_get_snapshot()
+            .returning(move || Ok(snapshot.clone()));
+        let rg = create_reader_group(mock_rg_state);
+        let mut rx = rg.notifications();
+        // the subscribers share the same poller.
+        let mut rx2 = rg.notifications();
+        assert_eq!(
+            rg.notifier
+                .lock()
+                .unwrap()
+                .as_ref()
+                .expect("notification poller")
+                .receiver_count(),
+            2
+        );
+        rg.client_factory.get_runtime().block_on(async {
+            assert_eq!(
+                rx.recv().await.expect("receive notification"),
+                ReaderGroupNotification::ReadersChanged {
+                    joined: vec![reader.clone()],
+                    left: vec![]
+                }
+            );
+            assert_eq!(
+                rx2.recv().await.expect("receive notification"),
+                ReaderGroupNotification::ReadersChanged {
+                    joined: vec![reader],
+                    left: vec![]
+                }
+            );
+            assert_eq!(
+                rx.recv().await.expect("receive notification"),
+                ReaderGroupNotification::SegmentCountChanged {
+                    segments: 2,
+                    readers: 1
+                }
+            );
+            assert_eq!(
+                rx.recv().await.expect("receive notification"),
+                ReaderGroupNotification::EndOfData
+            );
+
+            // the poller stops once all the receivers are dropped.
+            drop(rx);
+            drop(rx2);
+            while rg.notifier.lock().unwrap().is_some() {
+                sleep(Duration::from_millis(10)).await;
+            }
+        });
+    }
+
+    #[test]
+    fn test_add_and_remove_stream() {
+        let stream = ScopedStream::from("scope/s2");
+        let segment = ScopedSegment::from("scope/s2/0.#epoch.0");
+        let mut expected_segments = HashMap::new();
+        expected_segments.insert(segment.clone(), Offset::new(10));
+        let initial_config = ReaderGroupConfigBuilder::default()
+            .add_stream(ScopedStream::from("scope/s1"))
+            .build();
+        let mut added_config = initial_config.clone();
+        added_config
+            .config
+            .add_stream(stream.clone(), StreamCutVersioned::UNBOUNDED);
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state
+            .expect_add_stream()
+            .with(
+                predicate::eq(stream.clone()),
+                predicate::always(),
+                predicate::eq(expected_segments),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock_rg_state
+            .expect_remove_stream()
+            .with(predicate::eq(stream.clone()))
+            .times(1)
+            .returning(|_| Ok(()));
+        // the config stored in the reader group state is read after every update.
+        let mut configs = vec![initial_config.config, added_config.config];
+        mock_rg_state
+            .expect_get_config()
+            .times(2)
+            .returning(move || Ok(configs.pop().expect("config")));
+        let mut rg = create_reader_group(mock_rg_state);
+        let client_factory = rg.client_factory.clone();
+
+        let mut offsets = HashMap::new();
+        offsets.insert(segment.segment.number, 10);
+        client_factory
+            .get_runtime()
+            .block_on(rg.add_stream(stream.clone(), StreamCut::new(stream.clone(), offsets)))
+            .expect("add stream");
+        assert_eq!(rg.config.get_streams().len(), 2);
+        assert!(rg.config.get_streams().contains(&stream));
+
+        client_factory
+            .get_runtime()
+            .block_on(rg.remove_stream(&stream))
+            .expect("remove stream");
+        assert_eq!(rg.config.get_streams(), vec![ScopedStream::from("scope/s1")]);
+    }
+
+    #[test]
+    fn test_reset_to_time() {
+        let stream = ScopedStream::from("scope/s1");
+        let mut expected_segments = HashMap::new();
+        expected_segments.insert(ScopedSegment::from("scope/s1/0.#epoch.0"), Offset::new(0));
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state
+            .expect_reset()
+            .with(predicate::always(), predicate::eq(expected_segments))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let mut rg = create_reader_group(mock_rg_state);
+        let client_factory = rg.client_factory.clone();
+        client_factory
+            .get_runtime()
+            .block_on(create_stream(&client_factory, "scope", "s1"));
+
+        let time = SystemTime::now();
+        let stream_cuts = client_factory
+            .get_runtime()
+            .block_on(rg.get_stream_cuts_at_time(time))
+            .expect("get stream cuts at time");
+        assert_eq!(stream_cuts.len(), 1);
+        assert_eq!(stream_cuts[&stream].segment_offset_map.get(&0), Some(&0));
+
+        client_factory
+            .get_runtime()
+            .block_on(rg.reset_to_time(time))
+            .expect("reset to time");
+        match &rg.config.get_starting_stream_cuts()[&stream] {
+            StreamCutVersioned::V1(cut) => assert_eq!(cut.get_positions().len(), 1),
+            _ => panic!("starting stream cut should be bounded"),
+        }
+    }
+
+    #[test]
+    fn test_reset_to_tail() {
+        let stream = ScopedStream::from("scope/s1");
+        let segment = ScopedSegment::from("scope/s1/0.#epoch.0");
+        let mut expected_segments = HashMap::new();
+        expected_segments.insert(segment.clone(), Offset::new(100));
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state
+            .expect_reset()
+            .with(predicate::always(), predicate::eq(expected_segments))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let mut rg = create_reader_group(mock_rg_state);
+        let client_factory = rg.client_factory.clone();
+        client_factory
+            .get_runtime()
+            .block_on(create_stream(&client_factory, "scope", "s1"));
+        let mut writer = client_factory.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 100]).expect("write to segment");
+        writer.flush().expect("flush segment");
+
+        let rg_config = ReaderGroupConfigBuilder::default()
+            .set_start_position(StartPosition::Tail)
+            .add_stream(stream.clone())
+            .build();
+        assert_eq!(
+            rg_config.get_starting_stream_cuts()[&stream],
+            StreamCutVersioned::Tail
+        );
+        client_factory
+            .get_runtime()
+            .block_on(rg.reset(rg_config))
+            .expect("reset to tail");
+        // the stored config keeps the offsets of the tail.
+        match &rg.config.get_starting_stream_cuts()[&stream] {
+            StreamCutVersioned::V1(cut) => assert_eq!(cut.get_positions().get(&segment), Some(&100)),
+            _ => panic!("starting stream cut should be bounded"),
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut assigned = HashMap::new();
+        let mut reader_segments = HashMap::new();
+        reader_segments.insert(ScopedSegment::from("scope/s1/1.#epoch.0"), Offset::new(20));
+        reader_segments.insert(ScopedSegment::from("scope/s1/0.#epoch.0"), Offset::new(10));
+        assigned.insert(Reader::from("r2".to_string()), HashMap::new());
+        assigned.insert(Reader::from("r1".to_string()), reader_segments);
+        let mut unassigned = HashMap::new();
+        unassigned.insert(ScopedSegment::from("scope/s1/2.#epoch.0"), Offset::new(30));
+        let successor = ScopedSegment::from("scope/s1/3.#epoch.1");
+        let mut future = HashMap::new();
+        let mut predecessors = HashSet::new();
+        predecessors.insert(ScopedSegment::from("scope/s1/2.#epoch.0").segment);
+        future.insert(successor.clone(), predecessors);
+        // the configuration stored in the state has been updated by another instance.
+        let stored_config = ReaderGroupConfigBuilder::default()
+            .add_stream(ScopedStream::from("scope/s1"))
+            .add_stream(ScopedStream::from("scope/s2"))
+            .build();
+        let snapshot = ReaderGroupSnapshot {
+            config: stored_config.config.clone(),
+            assigned,
+            unassigned,
+            future,
+            end_of_stream: false,
+        };
+        let mut mock_rg_state = ReaderGroupState::default();
+        // the description is built from a single snapshot of the state.
+        mock_rg_state
+            .expect_get_snapshot()
+            .times(1)
+            .return_once(move || Ok(snapshot));
+        let rg = create_reader_group(mock_rg_state);
+
+        let info = rg
+            .client_factory
+            .get_runtime()
+            .block_on(rg.describe())
+            .expect("describe reader group");
+        assert_eq!(info.name, "rg");
+        assert_eq!(info.config, stored_config);
+        assert_eq!(info.readers.len(), 2);
+        assert_eq!(info.readers[0].reader, Reader::from("r1".to_string()));
+        assert_eq!(
+            info.readers[0].segments,
+            vec![
+                SegmentInfo {
+                    segment: ScopedSegment::from("scope/s1/0.#epoch.0"),
+                    offset: 10
+                },
+                SegmentInfo {
+                    segment: ScopedSegment::from("scope/s1/1.#epoch.0"),
+                    offset: 20
+                }
+            ]
+        );
+        assert!(info.readers[1].segments.is_empty());
+        assert_eq!(
+            info.unassigned_segments,
+            vec![SegmentInfo {
+                segment: ScopedSegment::from("scope/s1/2.#epoch.0"),
+                offset: 30
+            }]
+        );
+        assert_eq!(info.future_segments.len(), 1);
+        assert_eq!(info.future_segments[0].segment, successor);
+
+        let bytes = serde_cbor::to_vec(&info).expect("serialize reader group info");
+        let deserialized: ReaderGroupInfo =
+            serde_cbor::from_slice(&bytes).expect("deserialize reader group info");
+        assert_eq!(deserialized, info);
+    }
+
+    #[test]
+    fn test_unread_bytes() {
+        let reader = Reader::from("r1".to_string());
+        let segment = ScopedSegment::from("scope/s1/0.#epoch.0");
+        let mut assigned = HashMap::new();
+        let mut reader_segments = HashMap::new();
+        reader_segments.insert(segment.clone(), Offset::new(40));
+        assigned.insert(reader.clone(), reader_segments);
+        let snapshot = ReaderGroupSnapshot {
+            config: ReaderGroupConfigBuilder::default()
+                .add_stream(ScopedStream::from("scope/s1"))
+                .build()
+                .config,
+            assigned,
+            unassigned: HashMap::new(),
+            future: HashMap::new(),
+            end_of_stream: false,
+        };
+        // the unread bytes are computed from a single snapshot and nothing is written to the state.
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state
+            .expect_get_snapshot()
+            .times(1)
+            .return_once(move || Ok(snapshot));
+        let rg = create_reader_group(mock_rg_state);
+        let runtime = rg.client_factory.get_runtime();
+        runtime.block_on(create_stream(&rg.client_factory, "scope", "s1"));
+        let mut writer = rg.client_factory.create_byte_stream_writer(segment);
+        writer.write_all(&[1; 100]).expect("write to segment");
+        writer.flush().expect("flush segment");
+
+        let unread = runtime.block_on(rg.unread_bytes()).expect("get unread bytes");
+        let mut expected = HashMap::new();
+        expected.insert(reader, 60u64);
+        assert_eq!(
+            unread,
+            UnreadBytes {
+                readers: expected,
+                total: 60
+            }
+        );
+    }
+
+    #[test]
+    fn test_reset_reader_group() {
+        let mut mock_rg_state = ReaderGroupState::default();
+        mock_rg_state.expect_reset().return_once(|_, segments| {
+            assert_eq!(
+                segments.len(),
+                1,
+                "should reset to the head segments of the stream"
+            );
+            Ok(())
+        });
+        let mut rg = create_reader_group(mock_rg_state);
+        let client_factory = rg.client_factory.clone();
+        client_factory
+            .get_runtime()
+            .block_on(create_stream(&client_factory, "scope", "s2"));
+        let new_config = ReaderGroupConfigBuilder::default()
+            .add_stream(ScopedStream::from("scope/s2"))
+            .build();
         client_factory
             .get_runtime()
-            .block_on(rg.create_reader("r1".to_string()));
+            .block_on(rg.reset(new_config.clone()))
+            .expect("reset reader group");
+        assert_eq!(rg.config, new_config);
     }
 }
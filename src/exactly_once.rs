@@ -0,0 +1,738 @@
+//
+// Copyright (c) Dell Inc., or its subsidiaries. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+
+use crate::error::*;
+use crate::event_reader::{EventReader, EventReaderError};
+use crate::event_reader_group::ReaderGroup;
+use crate::reader_group::reader_group_state::ReaderGroupStateError;
+use crate::segment_slice::{Event, SegmentSlice};
+use crate::tablemap::{TableError, TableMap};
+use crate::transaction::transactional_event_stream_writer::TransactionalEventStreamWriter;
+use futures::pin_mut;
+use futures::stream::StreamExt;
+use pravega_client_shared::{Reader, ScopedStream, StreamCut, Timestamp, TransactionStatus, TxId};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+#[derive(Debug, Snafu)]
+pub enum ExactlyOnceError {
+    #[snafu(display("Failed to access the checkpoint table: {}", source))]
+    CheckpointTable { source: TableError },
+    #[snafu(display("Failed to begin a transaction: {}", source))]
+    BeginTransaction {
+        source: TransactionalEventStreamWriterError,
+    },
+    #[snafu(display("Transaction {} failed: {}", txn_id, source))]
+    TransactionFailed { txn_id: TxId, source: TransactionError },
+    #[snafu(display("Failed to abort transaction {}: {}", txn_id, source))]
+    AbortTransaction {
+        txn_id: TxId,
+        source: TransactionalEventStreamWriterError,
+    },
+    #[snafu(display("Failed to read events: {}", source))]
+    ReadEvents { source: EventReaderError },
+    #[snafu(display("Failed to update the reader group: {}", source))]
+    UpdateReaderGroup { source: ReaderGroupStateError },
+    #[snafu(display("Failed to process event: {}", error_msg))]
+    Process { error_msg: String },
+}
+
+// The keys of the checkpoint table.
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+enum CheckpointKey {
+    // the positions of the last committed transaction.
+    Committed,
+    // the transaction which is in progress or whose completion has not been recorded yet.
+    Transaction(String),
+}
+
+///
+/// The record stored in the checkpoint table for a transaction. The positions are the reader
+/// group checkpoint taken after the input events of the transaction have been read, they are
+/// saved right before the transaction is committed.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TransactionCheckpoint {
+    pub sequence: u64,
+    pub positions: Option<HashMap<ScopedStream, HashMap<i64, i64>>>,
+}
+
+impl TransactionCheckpoint {
+    fn to_stream_cuts(&self) -> Option<HashMap<ScopedStream, StreamCut>> {
+        self.positions.as_ref().map(|positions| {
+            positions
+                .iter()
+                .map(|(stream, offsets)| (stream.clone(), StreamCut::new(stream.clone(), offsets.clone())))
+                .collect()
+        })
+    }
+}
+
+//
+// CheckpointStore keeps the checkpoints of the transactions in a TableMap keyed by transaction id.
+//
+struct CheckpointStore {
+    table: TableMap,
+}
+
+impl CheckpointStore {
+    async fn begin(&self, txn_id: TxId, sequence: u64) -> Result<(), ExactlyOnceError> {
+        let checkpoint = TransactionCheckpoint {
+            sequence,
+            positions: None,
+        };
+        self.table
+            .insert(&CheckpointKey::Transaction(txn_id.0.to_string()), &checkpoint, -1)
+            .await
+            .context(CheckpointTable {})?;
+        Ok(())
+    }
+
+    async fn prepare(
+        &self,
+        txn_id: TxId,
+        checkpoint: &TransactionCheckpoint,
+    ) -> Result<(), ExactlyOnceError> {
+        self.table
+            .insert(&CheckpointKey::Transaction(txn_id.0.to_string()), checkpoint, -1)
+            .await
+            .context(CheckpointTable {})?;
+        Ok(())
+    }
+
+    async fn complete(
+        &self,
+        txn_id: TxId,
+        checkpoint: Option<&TransactionCheckpoint>,
+    ) -> Result<(), ExactlyOnceError> {
+        if let Some(checkpoint) = checkpoint {
+            self.table
+                .insert(&CheckpointKey::Committed, checkpoint, -1)
+                .await
+                .context(CheckpointTable {})?;
+        }
+        self.table
+            .remove(&CheckpointKey::Transaction(txn_id.0.to_string()), -1)
+            .await
+            .context(CheckpointTable {})
+    }
+
+    //
+    // Complete the transactions recorded in the table. The checkpoints of the committed
+    // transactions are kept and the other transactions are aborted. Returns the last committed
+    // checkpoint and whether any transaction was found.
+    //
+    async fn recover(
+        &self,
+        writer: &TransactionalEventStreamWriter,
+    ) -> Result<(Option<TransactionCheckpoint>, bool), ExactlyOnceError> {
+        // the delta of the table also contains the keys which have been removed since.
+        let mut keys = HashSet::new();
+        let entries = self
+            .table
+            .read_entries_stream_from_position::<CheckpointKey, TransactionCheckpoint>(10, 0);
+        pin_mut!(entries);
+        while let Some(entry) = entries.next().await {
+            let (key, _checkpoint, _version, _position) = entry.context(CheckpointTable {})?;
+            keys.insert(key);
+        }
+
+        let mut committed: Option<TransactionCheckpoint> = self
+            .table
+            .get(&CheckpointKey::Committed)
+            .await
+            .context(CheckpointTable {})?
+            .map(|(checkpoint, _version)| checkpoint);
+        let mut completed = vec![];
+        let mut updated = false;
+        for key in keys {
+            let txn_id = match key {
+                CheckpointKey::Transaction(id) => match id.parse() {
+                    Ok(id) => TxId(id),
+                    Err(e) => {
+                        warn!(
+                            "ignoring invalid transaction id {} in checkpoint table: {}",
+                            id, e
+                        );
+                        continue;
+                    }
+                },
+                CheckpointKey::Committed => continue,
+            };
+            let checkpoint: TransactionCheckpoint = match self
+                .table
+                .get(&CheckpointKey::Transaction(txn_id.0.to_string()))
+                .await
+                .context(CheckpointTable {})?
+            {
+                Some((checkpoint, _version)) => checkpoint,
+                None => continue,
+            };
+            completed.push(txn_id);
+            let txn = writer.get_txn(txn_id).await.context(BeginTransaction {})?;
+            let status = txn.check_status().await.context(TransactionFailed { txn_id })?;
+            match status {
+                TransactionStatus::Committing | TransactionStatus::Committed
+                    if checkpoint.positions.is_some() =>
+                {
+                    debug!("recovered checkpoint of committed transaction {}", txn_id);
+                    if committed
+                        .as_ref()
+                        .is_none_or(|last| last.sequence < checkpoint.sequence)
+                    {
+                        committed = Some(checkpoint);
+                        updated = true;
+                    }
+                }
+                TransactionStatus::Open => {
+                    info!("aborting orphaned transaction {}", txn_id);
+                    writer
+                        .abort_txn(txn_id)
+                        .await
+                        .context(AbortTransaction { txn_id })?;
+                }
+                _ => debug!("transaction {} is {:?}", txn_id, status),
+            }
+        }
+
+        // record the last committed checkpoint before the transactions are removed.
+        if updated {
+            if let Some(checkpoint) = &committed {
+                self.table
+                    .insert(&CheckpointKey::Committed, checkpoint, -1)
+                    .await
+                    .context(CheckpointTable {})?;
+            }
+        }
+        for txn_id in &completed {
+            self.complete(*txn_id, None).await?;
+        }
+        Ok((committed, !completed.is_empty()))
+    }
+}
+
+///
+/// ExactlyOnceProcessor reads events from a reader group, processes them and writes the outputs to
+/// a stream with exactly once semantics. The outputs of the events of every segment slice are
+/// written within a transaction, and the reader group checkpoint taken after reading the slice is
+/// saved in a TableMap keyed by the transaction id before the transaction is committed.
+///
+/// When the processor is created, or after a failure, the transactions left in the table are
+/// completed: open transactions are aborted and the readers are reset to the checkpoint of the last
+/// committed transaction, so every input event is reflected exactly once in the output stream.
+///
+/// The processor owns the only reader of the reader group, and the TableMap should not be shared
+/// with other processors.
+///
+/// ```no_run
+/// use pravega_client::client_factory::ClientFactory;
+/// use pravega_client::exactly_once::ExactlyOnceProcessor;
+/// use pravega_client_shared::{Scope, ScopedStream, WriterId};
+///
+/// async fn run(factory: ClientFactory) {
+///     let reader_group = factory
+///         .create_reader_group(Scope::from("scope".to_string()), "rg".to_string(), ScopedStream::from("scope/input"))
+///         .await
+///         .expect("create reader group");
+///     let writer = factory
+///         .create_transactional_event_stream_writer(ScopedStream::from("scope/output"), WriterId(0))
+///         .await;
+///     let checkpoints = factory
+///         .create_table_map(Scope::from("scope".to_string()), "rg-checkpoints".to_string())
+///         .await;
+///     let mut processor = ExactlyOnceProcessor::new(reader_group, "reader".to_string(), writer, checkpoints)
+///         .await
+///         .expect("create processor");
+///     loop {
+///         processor
+///             .process(|event| {
+///                 String::from_utf8(event.value.clone()).map(|value| vec![(None, value.to_uppercase().into_bytes())])
+///             })
+///             .await
+///             .expect("process events");
+///     }
+/// }
+/// ```
+///
+pub struct ExactlyOnceProcessor {
+    reader_group: ReaderGroup,
+    reader_id: String,
+    reader: Option<EventReader>,
+    writer: TransactionalEventStreamWriter,
+    checkpoints: CheckpointStore,
+    sequence: u64,
+    needs_recovery: bool,
+}
+
+impl ExactlyOnceProcessor {
+    ///
+    /// Create an ExactlyOnceProcessor. The transactions left by a previous processor using the
+    /// same TableMap are completed and the reader group is reset to the last committed checkpoint
+    /// before the reader is created.
+    ///
+    pub async fn new(
+        reader_group: ReaderGroup,
+        reader_id: String,
+        writer: TransactionalEventStreamWriter,
+        checkpoints: TableMap,
+    ) -> Result<Self, ExactlyOnceError> {
+        let mut processor = ExactlyOnceProcessor {
+            reader_group,
+            reader_id,
+            reader: None,
+            writer,
+            checkpoints: CheckpointStore { table: checkpoints },
+            sequence: 0,
+            needs_recovery: false,
+        };
+        processor.recover().await?;
+        Ok(processor)
+    }
+
+    ///
+    /// Get the reader group read by the processor.
+    ///
+    pub fn get_reader_group(&self) -> &ReaderGroup {
+        &self.reader_group
+    }
+
+    ///
+    /// Process the events of the next segment slice. The outputs returned by process for every
+    /// event are written with their routing keys in a transaction which is committed along with
+    /// the positions of the reader. Returns false if no segment slice is available.
+    ///
+    /// If process fails the transaction is aborted and the events of the slice are read again by
+    /// the next call. If any other step fails, the next call completes the pending transaction and
+    /// resets the reader to the last committed checkpoint first.
+    ///
+    pub async fn process<E, F>(&mut self, process: F) -> Result<bool, ExactlyOnceError>
+    where
+        E: Display,
+        F: FnMut(&Event) -> Result<Vec<(Option<String>, Vec<u8>)>, E>,
+    {
+        if self.needs_recovery {
+            self.recover().await?;
+        }
+        let reader = self.reader.as_mut().expect("reader is created by recovery");
+        let slice = match reader.acquire_segment().await.context(ReadEvents {})? {
+            Some(slice) => slice,
+            None => return Ok(false),
+        };
+        let result = self.process_slice(slice, process).await;
+        if let Err(e) = &result {
+            if !matches!(e, ExactlyOnceError::Process { .. }) {
+                warn!("failed to process segment slice: {}", e);
+                self.needs_recovery = true;
+            }
+        }
+        result.map(|_| true)
+    }
+
+    async fn process_slice<E, F>(
+        &mut self,
+        mut slice: SegmentSlice,
+        mut process: F,
+    ) -> Result<(), ExactlyOnceError>
+    where
+        E: Display,
+        F: FnMut(&Event) -> Result<Vec<(Option<String>, Vec<u8>)>, E>,
+    {
+        let start_offset = slice.meta.read_offset;
+        let mut txn = self.writer.begin().await.context(BeginTransaction {})?;
+        let txn_id = txn.get_txn_id();
+        self.sequence += 1;
+        self.checkpoints.begin(txn_id, self.sequence).await?;
+
+        while let Some(mut event) = slice.next() {
+            let outputs = match process(&event) {
+                Ok(outputs) => outputs,
+                Err(e) => {
+                    // abort the transaction and read the events of the slice again.
+                    txn.abort().await.context(TransactionFailed { txn_id })?;
+                    self.checkpoints.complete(txn_id, None).await?;
+                    let reader = self.reader.as_mut().expect("reader is created by recovery");
+                    reader
+                        .release_segment_at(slice, start_offset)
+                        .await
+                        .context(UpdateReaderGroup {})?;
+                    return Err(ExactlyOnceError::Process {
+                        error_msg: e.to_string(),
+                    });
+                }
+            };
+            for (routing_key, output) in outputs {
+                txn.write_event(routing_key, output)
+                    .await
+                    .context(TransactionFailed { txn_id })?;
+            }
+            // the positions are tracked by the checkpoints of the transactions.
+            if let Some(ack_handle) = event.ack_handle.take() {
+                ack_handle.ack();
+            }
+        }
+
+        let reader = self.reader.as_mut().expect("reader is created by recovery");
+        reader
+            .release_segment(slice)
+            .await
+            .context(UpdateReaderGroup {})?;
+        reader.publish_positions().await.context(UpdateReaderGroup {})?;
+        let stream_cuts = self
+            .reader_group
+            .generate_stream_cuts()
+            .await
+            .context(UpdateReaderGroup {})?;
+        let checkpoint = TransactionCheckpoint {
+            sequence: self.sequence,
+            positions: Some(
+                stream_cuts
+                    .into_iter()
+                    .map(|(stream, cut)| (stream, cut.segment_offset_map))
+                    .collect(),
+            ),
+        };
+        self.checkpoints.prepare(txn_id, &checkpoint).await?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        txn.commit(Timestamp(timestamp))
+            .await
+            .context(TransactionFailed { txn_id })?;
+        self.checkpoints.complete(txn_id, Some(&checkpoint)).await
+    }
+
+    // Complete the transactions left in the checkpoint table and restart reading from the last
+    // committed checkpoint with a new reader.
+    async fn recover(&mut self) -> Result<(), ExactlyOnceError> {
+        let (committed, found) = self.checkpoints.recover(&self.writer).await?;
+        if let Some(checkpoint) = &committed {
+            self.sequence = checkpoint.sequence;
+        }
+
+        let rewind = found || self.reader.is_some();
+        match self.reader.take() {
+            Some(mut reader) => reader.close().await.context(UpdateReaderGroup {})?,
+            None => {
+                let reader = Reader::from(self.reader_id.clone());
+                let online = self
+                    .reader_group
+                    .describe()
+                    .await
+                    .context(UpdateReaderGroup {})?
+                    .readers
+                    .iter()
+                    .any(|info| info.reader == reader);
+                if online {
+                    // the reader of a previous processor is still online.
+                    self.reader_group
+                        .reader_offline(self.reader_id.clone(), HashMap::new())
+                        .await
+                        .context(UpdateReaderGroup {})?;
+                }
+            }
+        }
+
+        match committed
+            .as_ref()
+            .and_then(|checkpoint| checkpoint.to_stream_cuts())
+        {
+            Some(stream_cuts) => {
+                info!(
+                    "resetting reader group to the last committed checkpoint {:?}",
+                    stream_cuts
+                );
+                self.reader_group
+                    .reset_to_stream_cuts(stream_cuts)
+                    .await
+                    .context(UpdateReaderGroup {})?;
+            }
+            None if rewind => {
+                // no transaction has been committed, start from the beginning again.
+                let config = self
+                    .reader_group
+                    .describe()
+                    .await
+                    .context(UpdateReaderGroup {})?
+                    .config;
+                self.reader_group
+                    .reset(config)
+                    .await
+                    .context(UpdateReaderGroup {})?;
+            }
+            None => {}
+        }
+        self.reader = Some(
+            self.reader_group
+                .create_reader(self.reader_id.clone())
+                .await
+                .context(UpdateReaderGroup {})?,
+        );
+        self.needs_recovery = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_factory::ClientFactory;
+    use crate::create_stream;
+    use crate::reader_group::reader_group_state::{MockReaderGroupState, Offset, ReaderGroupSnapshot};
+    use crate::reader_group_config::ReaderGroupConfigBuilder;
+    use crate::segment_slice::{SegmentDataBuffer, SliceMetadata};
+    use bytes::{BufMut, BytesMut};
+    use mockall::predicate;
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use pravega_client_shared::{Scope, ScopedSegment, WriterId};
+    use pravega_wire_protocol::commands::{Command, EventCommand};
+    use std::sync::Arc;
+    use tokio::sync::{mpsc, Mutex};
+
+    #[test]
+    fn test_recover_checkpoints() {
+        let factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        factory.get_runtime().block_on(async {
+            create_stream(&factory, "scope", "exactlyonce").await;
+            let mut writer = factory
+                .create_transactional_event_stream_writer(
+                    ScopedStream::from("scope/exactlyonce"),
+                    WriterId(0),
+                )
+                .await;
+            let store = CheckpointStore {
+                table: factory
+                    .create_table_map(Scope::from("scope".to_string()), "checkpoints".to_string())
+                    .await,
+            };
+
+            // a committed transaction whose completion has not been recorded.
+            let mut committed = writer.begin().await.expect("begin transaction");
+            let checkpoint = create_checkpoint(2, 20);
+            store.begin(committed.get_txn_id(), 2).await.expect("begin");
+            store
+                .prepare(committed.get_txn_id(), &checkpoint)
+                .await
+                .expect("prepare");
+            committed.commit(Timestamp(0)).await.expect("commit");
+            // an older committed transaction.
+            let mut completed = writer.begin().await.expect("begin transaction");
+            store.begin(completed.get_txn_id(), 1).await.expect("begin");
+            completed.commit(Timestamp(0)).await.expect("commit");
+            store
+                .complete(completed.get_txn_id(), Some(&create_checkpoint(1, 10)))
+                .await
+                .expect("complete");
+            // an orphaned transaction.
+            let orphaned = writer.begin().await.expect("begin transaction");
+            store.begin(orphaned.get_txn_id(), 3).await.expect("begin");
+
+            let (recovered, found) = store.recover(&writer).await.expect("recover");
+            assert!(found);
+            assert_eq!(recovered, Some(checkpoint.clone()));
+            assert_eq!(
+                orphaned.check_status().await.expect("check status"),
+                TransactionStatus::Aborted
+            );
+
+            // the transactions are removed once they are completed.
+            let (recovered, found) = store.recover(&writer).await.expect("recover");
+            assert!(!found);
+            assert_eq!(recovered, Some(checkpoint));
+        });
+    }
+
+    #[test]
+    fn test_process_and_recover() {
+        let factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        // the data of the reader is received from the channel.
+        let reader_factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let stream = ScopedStream::from("scope/eoinput");
+        let segment = ScopedSegment::from("scope/eoinput/0.#epoch.0");
+        factory.get_runtime().block_on(async {
+            create_stream(&factory, "scope", "eoinput").await;
+            create_stream(&factory, "scope", "eooutput").await;
+            let config = ReaderGroupConfigBuilder::default()
+                .add_stream(stream.clone())
+                .build();
+            let mut rg_state = MockReaderGroupState::default();
+            let state_config = config.config.clone();
+            rg_state
+                .expect_get_config()
+                .returning(move || Ok(state_config.clone()));
+            let snapshot = ReaderGroupSnapshot {
+                config: config.config.clone(),
+                assigned: HashMap::new(),
+                unassigned: HashMap::new(),
+                future: HashMap::new(),
+                end_of_stream: false,
+            };
+            rg_state
+                .expect_get_snapshot()
+                .returning(move || Ok(snapshot.clone()));
+            rg_state.expect_add_reader().returning(|_| Ok(()));
+            rg_state.expect_remove_reader().returning(|_, _| Ok(()));
+            rg_state.expect_get_generation().returning(|| Ok(0u64));
+            rg_state
+                .expect_compute_segments_to_acquire_or_release()
+                .returning(|_| Ok(0isize));
+            rg_state
+                .expect_get_segments_for_reader()
+                .returning(|_| Ok(HashSet::new()));
+            rg_state.expect_update_reader_positions().returning(|_, _| Ok(()));
+            let cut_stream = stream.clone();
+            rg_state.expect_get_stream_cuts().returning(move || {
+                let mut offsets = HashMap::new();
+                offsets.insert(0, 27);
+                let mut stream_cuts = HashMap::new();
+                stream_cuts.insert(cut_stream.clone(), StreamCut::new(cut_stream.clone(), offsets));
+                Ok(stream_cuts)
+            });
+            // the reader group is reset to the committed checkpoint by the recovery.
+            let mut expected_segments = HashMap::new();
+            expected_segments.insert(segment.clone(), Offset::new(27));
+            rg_state
+                .expect_reset()
+                .with(predicate::always(), predicate::eq(expected_segments))
+                .times(1)
+                .returning(|_, _| Ok(()));
+            let reader_group = ReaderGroup::new(
+                "rg".to_string(),
+                config,
+                Arc::new(Mutex::new(rg_state)),
+                factory.clone(),
+            );
+            let writer = factory
+                .create_transactional_event_stream_writer(ScopedStream::from("scope/eooutput"), WriterId(0))
+                .await;
+            let table = factory
+                .create_table_map(Scope::from("scope".to_string()), "eocheckpoints".to_string())
+                .await;
+            let mut processor = ExactlyOnceProcessor::new(reader_group, "reader".to_string(), writer, table)
+                .await
+                .expect("create processor");
+
+            // replace the reader by one which receives the data from the channel.
+            let (tx, rx) = mpsc::channel(1);
+            let mut slices = HashMap::new();
+            slices.insert(
+                segment.clone(),
+                SliceMetadata {
+                    scoped_segment: segment.to_string(),
+                    ..Default::default()
+                },
+            );
+            processor.reader = Some(EventReader::init_event_reader(
+                processor.reader_group.state.clone(),
+                Reader::from("reader".to_string()),
+                reader_factory.clone(),
+                tx.clone(),
+                rx,
+                slices,
+                HashMap::new(),
+            ));
+
+            // the events of the slice are read again after every failure.
+            let mut attempts = 0;
+            loop {
+                tx.send((
+                    processor
+                        .reader
+                        .as_ref()
+                        .expect("reader")
+                        .get_read_epoch(&segment),
+                    Ok(create_segment_data(&segment, &[b"event", b"poison"])),
+                ))
+                .await
+                .expect("send segment data");
+                let result = processor
+                    .process(|event| {
+                        if event.value == b"poison" {
+                            attempts += 1;
+                            if attempts < 3 {
+                                return Err("poison event");
+                            }
+                        }
+                        Ok(vec![(None, event.value.clone())])
+                    })
+                    .await;
+                match result {
+                    Ok(processed) => {
+                        assert!(processed);
+                        break;
+                    }
+                    Err(ExactlyOnceError::Process { .. }) => {}
+                    Err(e) => panic!("unexpected error {}", e),
+                }
+            }
+            assert_eq!(attempts, 3);
+            let (committed, found) = processor
+                .checkpoints
+                .recover(&processor.writer)
+                .await
+                .expect("recover checkpoints");
+            assert!(!found);
+            assert_eq!(committed.map(|checkpoint| checkpoint.sequence), Some(3));
+
+            processor.recover().await.expect("recover processor");
+            assert!(processor.reader.is_some());
+            assert_eq!(processor.sequence, 3);
+        });
+    }
+
+    // create the data of a segment holding the events from offset 0.
+    fn create_segment_data(segment: &ScopedSegment, events: &[&[u8]]) -> SegmentDataBuffer {
+        let mut value = BytesMut::new();
+        for event in events {
+            value.put_i32(EventCommand::TYPE_CODE);
+            value.put_i32(event.len() as i32);
+            value.put_slice(event);
+        }
+        SegmentDataBuffer {
+            segment: segment.to_string(),
+            offset_in_segment: 0,
+            value,
+        }
+    }
+
+    fn create_checkpoint(sequence: u64, offset: i64) -> TransactionCheckpoint {
+        let mut offsets = HashMap::new();
+        offsets.insert(0, offset);
+        let mut positions = HashMap::new();
+        positions.insert(ScopedStream::from("scope/input"), offsets);
+        TransactionCheckpoint {
+            sequence,
+            positions: Some(positions),
+        }
+    }
+}
@@ -55,14 +55,18 @@ use rand::{Rng, SeedableRng};
 use std::cell::RefCell;
 use std::sync::atomic::{AtomicI64, Ordering};
 
+pub mod ack_tracker;
+pub mod batch_client;
 pub mod byte_stream;
 pub mod client_factory;
+pub mod dead_letter;
 pub mod error;
-pub mod event_reader;
-pub mod event_stream_writer;
 #[macro_use]
 pub mod metric;
+pub mod event_reader;
 pub mod event_reader_group;
+pub mod event_stream_writer;
+pub mod exactly_once;
 pub mod raw_client;
 mod reactor;
 pub mod reader_group;
@@ -70,7 +74,7 @@ pub mod reader_group_config;
 pub mod segment_metadata;
 pub mod segment_reader;
 pub mod segment_slice;
-mod stream;
+pub mod stream;
 pub mod table_synchronizer;
 pub mod tablemap;
 pub mod trace;
@@ -19,6 +19,9 @@ pub enum ClientMetrics {
     ClientAppendLatency,
     ClientAppendBlockSize,
     ClientOutstandingAppendCount,
+    ReaderUnreadBytes,
+    ReaderGroupUnreadBytes,
+    ReaderTruncatedBytesSkipped,
 }
 
 impl ClientMetrics {
@@ -42,6 +45,24 @@ impl ClientMetrics {
                     "The current outstanding appends from caller."
                 );
             }
+            ClientMetrics::ReaderUnreadBytes => {
+                register_gauge!(
+                    "pravega.client.reader.unread_bytes",
+                    "The bytes a reader has yet to read from its assigned segments."
+                );
+            }
+            ClientMetrics::ReaderGroupUnreadBytes => {
+                register_gauge!(
+                    "pravega.client.reader_group.unread_bytes",
+                    "The bytes a reader group has yet to read from its streams."
+                );
+            }
+            ClientMetrics::ReaderTruncatedBytesSkipped => {
+                register_gauge!(
+                    "pravega.client.reader.truncated_bytes_skipped",
+                    "The bytes a reader skipped because its segments were truncated."
+                );
+            }
         }
     }
 }
@@ -70,6 +91,15 @@ macro_rules! update {
             ClientMetrics::ClientOutstandingAppendCount => {
                 metrics::gauge!("pravega.client.segment.outstanding_append_count", $value as f64, $($tags)*);
             }
+            ClientMetrics::ReaderUnreadBytes => {
+                metrics::gauge!("pravega.client.reader.unread_bytes", $value as f64, $($tags)*);
+            }
+            ClientMetrics::ReaderGroupUnreadBytes => {
+                metrics::gauge!("pravega.client.reader_group.unread_bytes", $value as f64, $($tags)*);
+            }
+            ClientMetrics::ReaderTruncatedBytesSkipped => {
+                metrics::gauge!("pravega.client.reader.truncated_bytes_skipped", $value as f64, $($tags)*);
+            }
         }
     };
 }
@@ -10,25 +10,40 @@
 
 use crate::client_factory::ClientFactory;
 use crate::error::*;
-use crate::reader_group_config::ReaderGroupConfigVersioned;
+use crate::reader_group_config::{ReaderGroupConfigVersioned, SegmentAssignmentStrategy, TruncationPolicy};
+use crate::segment_metadata::SegmentMetadataClientError;
+use crate::stream::stream_cut::StreamCutVersioned;
 use crate::table_synchronizer::{deserialize_from, Table, TableSynchronizer, Value};
+use crate::tablemap::TableError;
 #[cfg(test)]
 use mockall::automock;
-use pravega_client_shared::{Reader, Scope, ScopedSegment, Segment, SegmentWithRange};
+use pravega_client_retry::retry_result::RetryError;
+use pravega_client_shared::{
+    Reader, Scope, ScopedSegment, ScopedStream, Segment, StreamCut, StreamSegmentsWithPredecessors,
+};
+use pravega_controller_client::ControllerError;
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use snafu::{ensure, OptionExt, Snafu};
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
 const ASSUMED_LAG_MILLIS: u64 = 30000;
 const DEFAULT_INNER_KEY: &str = "default";
+// A reader is considered behind or ahead if its distance to tail differs from the average
+// by more than average / UNREAD_BYTES_TOLERANCE_DIVISOR.
+const UNREAD_BYTES_TOLERANCE_DIVISOR: u128 = 5;
 
+const CONFIG: &str = "config";
 const ASSIGNED: &str = "assigned_segments";
 const UNASSIGNED: &str = "unassigned_segments";
 const FUTURE: &str = "future_segments";
 const DISTANCE: &str = "distance_to_tail";
+const GENERATION: &str = "generation";
+const HEARTBEAT: &str = "heartbeats";
+const SEALED: &str = "sealed_segments";
 
 #[derive(Debug, Snafu)]
 pub enum ReaderGroupStateError {
@@ -37,6 +52,20 @@ pub enum ReaderGroupStateError {
         error_msg: String,
         source: SynchronizerError,
     },
+    #[snafu(display("Table error while performing {}: {}", error_msg, source))]
+    TableMapError { error_msg: String, source: TableError },
+    #[snafu(display("Segment metadata error while performing {}: {}", error_msg, source))]
+    SegmentMetadataError {
+        error_msg: String,
+        source: SegmentMetadataClientError,
+    },
+    #[snafu(display("Controller error while performing {}: {:?}", error_msg, err))]
+    ControllerRequestError {
+        error_msg: String,
+        err: RetryError<ControllerError>,
+    },
+    #[snafu(display("Reader {} is not online in the reader group", reader))]
+    ReaderOffline { reader: String },
 }
 
 /// ReaderGroupState encapsulates all readers states.
@@ -63,6 +92,17 @@ pub struct ReaderGroupState {
     ///
     /// Segments waiting to be assigned to readers.
     /// unassigned_segments: HashMap<ScopedSegment, Offset>
+    ///
+    /// Incremented every time the reader group is reset. Readers use it to detect that
+    /// their current assignments are no longer valid.
+    /// generation: u64
+    ///
+    /// The wall clock time in milliseconds of the last heartbeat sent by each online reader.
+    /// Readers whose last heartbeat is older than the reader lease are considered dead.
+    /// heartbeats: HashMap<Reader, u64>
+    ///
+    /// Completed segments which have no successors because their stream is sealed.
+    /// sealed_segments: HashSet<ScopedSegment>
     sync: TableSynchronizer,
 }
 
@@ -81,7 +121,7 @@ impl ReaderGroupState {
         sync.insert(move |table| {
             if table.is_empty() {
                 table.insert(
-                    "config".to_owned(),
+                    CONFIG.to_owned(),
                     DEFAULT_INNER_KEY.to_owned(),
                     "ReaderGroupConfigVersioned".to_owned(),
                     Box::new(config.clone()),
@@ -104,6 +144,293 @@ impl ReaderGroupState {
         ReaderGroupState { sync }
     }
 
+    /// Creates a ReaderGroupState on top of an existing TableSynchronizer without initializing it.
+    pub(crate) fn from_synchronizer(sync: TableSynchronizer) -> ReaderGroupState {
+        ReaderGroupState { sync }
+    }
+
+    /// Marks all the online readers offline and deletes the table segment that stores the
+    /// reader group state. The reader group cannot be used after it is deleted, deleting a
+    /// reader group state which does not exist has no effect.
+    pub(crate) async fn delete(&mut self) -> Result<(), ReaderGroupStateError> {
+        info!("Deleting reader group state {:?}", self.sync.get_name());
+        match self.sync.fetch_updates().await {
+            Err(TableError::TableDoesNotExist { .. }) => {
+                info!("reader group state {:?} is already deleted", self.sync.get_name());
+                return Ok(());
+            }
+            res => res.context(TableMapError {
+                error_msg: "fetch reader group state before deletion".to_owned(),
+            })?,
+        };
+        let _res_str = self
+            .sync
+            .insert(ReaderGroupState::remove_all_readers_internal)
+            .await
+            .context(SyncError {
+                error_msg: "remove all readers".to_owned(),
+            })?;
+        self.sync.delete().await.context(TableMapError {
+            error_msg: "delete reader group state table".to_owned(),
+        })
+    }
+
+    fn remove_all_readers_internal(table: &mut Table) -> Result<Option<String>, SynchronizerError> {
+        for reader in table.get_inner_map(ASSIGNED).keys() {
+            ReaderGroupState::remove_reader_internal(
+                table,
+                &Reader::from(reader.to_owned()),
+                &HashMap::new(),
+            )?;
+        }
+        Ok(None)
+    }
+
+    /// Resets the reader group state to the given config and starting segments.
+    /// All the readers stay online but lose their assigned segments. The unassigned and
+    /// future segments are replaced and the generation is incremented so that online readers
+    /// can detect the reset.
+    pub(crate) async fn reset(
+        &mut self,
+        config: ReaderGroupConfigVersioned,
+        segments_to_offsets: HashMap<ScopedSegment, Offset>,
+    ) -> Result<(), ReaderGroupStateError> {
+        info!("Resetting reader group state with config {:?}", config);
+        let _res_str = self
+            .sync
+            .insert(|table| ReaderGroupState::reset_internal(table, &config, &segments_to_offsets))
+            .await
+            .context(SyncError {
+                error_msg: format!("reset reader group to segments {:?}", segments_to_offsets),
+            })?;
+        Ok(())
+    }
+
+    fn reset_internal(
+        table: &mut Table,
+        config: &ReaderGroupConfigVersioned,
+        segments_to_offsets: &HashMap<ScopedSegment, Offset>,
+    ) -> Result<Option<String>, SynchronizerError> {
+        table.insert(
+            CONFIG.to_owned(),
+            DEFAULT_INNER_KEY.to_owned(),
+            "ReaderGroupConfigVersioned".to_owned(),
+            Box::new(config.clone()),
+        );
+
+        // readers stay online with no segments assigned.
+        let empty_map: HashMap<ScopedSegment, Offset> = HashMap::new();
+        for reader in table.get_inner_map(ASSIGNED).keys() {
+            table.insert(
+                ASSIGNED.to_owned(),
+                reader.to_owned(),
+                "HashMap<ScopedSegment, Offset>".to_owned(),
+                Box::new(empty_map.clone()),
+            );
+            table.insert(
+                DISTANCE.to_owned(),
+                reader.to_owned(),
+                "u64".to_owned(),
+                Box::new(u64::MAX),
+            );
+        }
+
+        for segment in table.get_inner_map(FUTURE).keys() {
+            table.insert_tombstone(FUTURE.to_owned(), segment.to_owned())?;
+        }
+
+        for segment in table.get_inner_map(SEALED).keys() {
+            table.insert_tombstone(SEALED.to_owned(), segment.to_owned())?;
+        }
+
+        // a segment cannot be tombstoned and inserted in the same update.
+        for segment in table.get_inner_map(UNASSIGNED).keys() {
+            if !segments_to_offsets.contains_key(&ScopedSegment::from(segment.as_str())) {
+                table.insert_tombstone(UNASSIGNED.to_owned(), segment.to_owned())?;
+            }
+        }
+        for (segment, offset) in segments_to_offsets {
+            table.insert(
+                UNASSIGNED.to_owned(),
+                segment.to_string(),
+                "Offset".to_owned(),
+                Box::new(offset.to_owned()),
+            );
+        }
+
+        let generation = ReaderGroupState::get_generation_internal(&table.get_inner_map(GENERATION));
+        table.insert(
+            GENERATION.to_owned(),
+            DEFAULT_INNER_KEY.to_owned(),
+            "u64".to_owned(),
+            Box::new(generation + 1),
+        );
+        Ok(None)
+    }
+
+    /// Adds a stream to the reader group. The stream is added to the config stored in the reader
+    /// group state and the given segments of the stream become unassigned in the same update, so
+    /// online readers acquire them at their next rebalance.
+    pub(crate) async fn add_stream(
+        &mut self,
+        stream: &ScopedStream,
+        stream_cut: StreamCutVersioned,
+        segments_to_offsets: HashMap<ScopedSegment, Offset>,
+    ) -> Result<(), ReaderGroupStateError> {
+        info!(
+            "Adding stream {:?} to reader group at {:?}",
+            stream, segments_to_offsets
+        );
+        let _res_str = self
+            .sync
+            .insert(|table| {
+                ReaderGroupState::add_stream_internal(table, stream, &stream_cut, &segments_to_offsets)
+            })
+            .await
+            .context(SyncError {
+                error_msg: format!("add stream {:?}", stream),
+            })?;
+        Ok(())
+    }
+
+    fn add_stream_internal(
+        table: &mut Table,
+        stream: &ScopedStream,
+        stream_cut: &StreamCutVersioned,
+        segments_to_offsets: &HashMap<ScopedSegment, Offset>,
+    ) -> Result<Option<String>, SynchronizerError> {
+        let mut config = ReaderGroupState::get_config_internal(&table.get_inner_map(CONFIG))?;
+        let belongs_to_stream =
+            |segment: &String| &ScopedStream::from(&ScopedSegment::from(segment.as_str())) == stream;
+        let assigned = ReaderGroupState::get_assigned_segments_internal(table.get_inner_map(ASSIGNED));
+        ensure!(
+            !config.contains_stream(stream)
+                && !table.get_inner_map(UNASSIGNED).keys().any(belongs_to_stream)
+                && !table.get_inner_map(FUTURE).keys().any(belongs_to_stream)
+                && !assigned
+                    .values()
+                    .flat_map(|segments| segments.keys())
+                    .any(|segment| &ScopedStream::from(segment) == stream),
+            SyncUpdateError {
+                error_msg: format!(
+                    "Failed to add stream {:?}: stream is already read by the reader group",
+                    stream
+                ),
+            }
+        );
+
+        config.add_stream(stream.clone(), stream_cut.clone());
+        table.insert(
+            CONFIG.to_owned(),
+            DEFAULT_INNER_KEY.to_owned(),
+            "ReaderGroupConfigVersioned".to_owned(),
+            Box::new(config),
+        );
+        for (segment, offset) in segments_to_offsets {
+            table.insert(
+                UNASSIGNED.to_owned(),
+                segment.to_string(),
+                "Offset".to_owned(),
+                Box::new(offset.to_owned()),
+            );
+        }
+        Ok(None)
+    }
+
+    /// Removes a stream from the reader group. The stream is removed from the config stored in
+    /// the reader group state and the segments of the stream are removed from the unassigned,
+    /// future and sealed segments and from the segments assigned to the readers in the same
+    /// update. Readers drop the removed segments at their next rebalance. Removing a stream
+    /// which is not read by the reader group is a no-op.
+    pub(crate) async fn remove_stream(&mut self, stream: &ScopedStream) -> Result<(), ReaderGroupStateError> {
+        info!("Removing stream {:?} from reader group", stream);
+        let _res_str = self
+            .sync
+            .insert(|table| ReaderGroupState::remove_stream_internal(table, stream))
+            .await
+            .context(SyncError {
+                error_msg: format!("remove stream {:?}", stream),
+            })?;
+        Ok(())
+    }
+
+    fn remove_stream_internal(
+        table: &mut Table,
+        stream: &ScopedStream,
+    ) -> Result<Option<String>, SynchronizerError> {
+        let mut config = ReaderGroupState::get_config_internal(&table.get_inner_map(CONFIG))?;
+        if !config.contains_stream(stream) {
+            info!("stream {:?} is not read by the reader group", stream);
+            return Ok(None);
+        }
+        config.remove_stream(stream);
+        table.insert(
+            CONFIG.to_owned(),
+            DEFAULT_INNER_KEY.to_owned(),
+            "ReaderGroupConfigVersioned".to_owned(),
+            Box::new(config),
+        );
+
+        for outer_key in &[UNASSIGNED, FUTURE, SEALED] {
+            for segment in table.get_inner_map(outer_key).keys() {
+                if &ScopedStream::from(&ScopedSegment::from(segment.as_str())) == stream {
+                    table.insert_tombstone(outer_key.to_string(), segment.to_owned())?;
+                }
+            }
+        }
+
+        let assigned = ReaderGroupState::get_assigned_segments_internal(table.get_inner_map(ASSIGNED));
+        for (reader, mut segments) in assigned {
+            let before = segments.len();
+            segments.retain(|segment, _| &ScopedStream::from(segment) != stream);
+            if segments.len() != before {
+                table.insert(
+                    ASSIGNED.to_owned(),
+                    reader.to_string(),
+                    "HashMap<ScopedSegment, Offset>".to_owned(),
+                    Box::new(segments),
+                );
+            }
+        }
+        Ok(None)
+    }
+
+    fn get_config_internal(
+        config: &HashMap<String, Value>,
+    ) -> Result<ReaderGroupConfigVersioned, SynchronizerError> {
+        let value = config.get(DEFAULT_INNER_KEY).context(SyncUpdateError {
+            error_msg: "reader group config is missing".to_owned(),
+        })?;
+        Ok(deserialize_from(&value.data).expect("deserialize config"))
+    }
+
+    /// Returns the reader group config stored in the reader group state.
+    pub(crate) async fn get_config(&mut self) -> Result<ReaderGroupConfigVersioned, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "fetch reader group config".to_owned(),
+        })?;
+        ReaderGroupState::get_config_internal(&self.sync.get_inner_map(CONFIG)).context(SyncError {
+            error_msg: "read reader group config".to_owned(),
+        })
+    }
+
+    /// Returns the generation of the reader group state. The generation changes every time
+    /// the reader group is reset.
+    pub async fn get_generation(&mut self) -> Result<u64, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "fetch generation".to_owned(),
+        })?;
+        Ok(ReaderGroupState::get_generation_internal(
+            &self.sync.get_inner_map(GENERATION),
+        ))
+    }
+
+    fn get_generation_internal(generation: &HashMap<String, Value>) -> u64 {
+        generation
+            .get(DEFAULT_INNER_KEY)
+            .map_or(0, |v| deserialize_from(&v.data).expect("deserialize generation"))
+    }
+
     /// Adds a reader to the reader group state.
     pub async fn add_reader(&mut self, reader: &Reader) -> Result<(), ReaderGroupStateError> {
         info!("Adding reader {:?} to reader group", reader);
@@ -141,13 +468,87 @@ impl ReaderGroupState {
             "u64".to_owned(),
             Box::new(u64::MAX),
         );
+
+        table.insert(
+            HEARTBEAT.to_owned(),
+            reader.to_string(),
+            "u64".to_owned(),
+            Box::new(current_time_millis()),
+        );
+        Ok(None)
+    }
+
+    /// Records a heartbeat for the given reader. Readers whose last heartbeat is older than the
+    /// reader lease of the reader group config are put offline in the same update, the segments
+    /// they owned are returned to the unassigned list at their last published offsets.
+    /// ReaderOffline is returned if the reader has been put offline, e.g. since its lease expired.
+    pub async fn heartbeat(&mut self, reader: &Reader) -> Result<(), ReaderGroupStateError> {
+        debug!("Heartbeat from reader {:?}", reader);
+        let result = self
+            .sync
+            .insert(|table| ReaderGroupState::heartbeat_internal(table, reader, current_time_millis()))
+            .await;
+        if result.is_err() && self.sync.get(ASSIGNED, &reader.to_string()).is_none() {
+            return Err(ReaderGroupStateError::ReaderOffline {
+                reader: reader.to_string(),
+            });
+        }
+        let _res_str = result.context(SyncError {
+            error_msg: format!("heartbeat from reader {:?}", reader),
+        })?;
+        Ok(())
+    }
+
+    fn heartbeat_internal(
+        table: &mut Table,
+        reader: &Reader,
+        now_millis: u64,
+    ) -> Result<Option<String>, SynchronizerError> {
+        ReaderGroupState::check_reader_online(&table.get_inner_map(ASSIGNED), reader)?;
+        table.insert(
+            HEARTBEAT.to_owned(),
+            reader.to_string(),
+            "u64".to_owned(),
+            Box::new(now_millis),
+        );
+
+        let lease = table
+            .get_inner_map(CONFIG)
+            .get(DEFAULT_INNER_KEY)
+            .map_or(u64::MAX, |v| {
+                let config: ReaderGroupConfigVersioned =
+                    deserialize_from(&v.data).expect("deserialize config");
+                config.get_reader_lease_millis()
+            });
+        let heartbeats = table.get_inner_map(HEARTBEAT);
+        for r in table.get_inner_map(ASSIGNED).keys() {
+            if let Some(v) = heartbeats.get(r) {
+                let last_heartbeat: u64 = deserialize_from(&v.data).expect("deserialize heartbeat");
+                if now_millis.saturating_sub(last_heartbeat) > lease {
+                    warn!(
+                        "reader {} has not sent a heartbeat for {} ms, putting it offline",
+                        r,
+                        now_millis - last_heartbeat
+                    );
+                    ReaderGroupState::remove_reader_internal(
+                        table,
+                        &Reader::from(r.to_owned()),
+                        &HashMap::new(),
+                    )?;
+                }
+            }
+        }
         Ok(None)
     }
 
     /// Returns the active readers in a vector.
-    pub async fn get_online_readers(&mut self) -> Vec<Reader> {
-        self.sync.fetch_updates().await.expect("should fetch updates");
-        ReaderGroupState::get_online_readers_internal(self.sync.get_inner_map(ASSIGNED))
+    pub async fn get_online_readers(&mut self) -> Result<Vec<Reader>, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "get online readers".to_owned(),
+        })?;
+        Ok(ReaderGroupState::get_online_readers_internal(
+            self.sync.get_inner_map(ASSIGNED),
+        ))
     }
 
     fn get_online_readers_internal(assigned_segments: HashMap<String, Value>) -> Vec<Reader> {
@@ -162,7 +563,9 @@ impl ReaderGroupState {
         &mut self,
         reader: &Reader,
     ) -> Result<HashMap<ScopedSegment, Offset>, SynchronizerError> {
-        self.sync.fetch_updates().await.expect("should fetch updates");
+        self.sync.fetch_updates().await.context(SyncTableError {
+            operation: "fetch reader positions".to_owned(),
+        })?;
         ReaderGroupState::get_reader_positions_internal(reader, self.sync.get_inner_map(ASSIGNED))
     }
 
@@ -267,15 +670,51 @@ impl ReaderGroupState {
         }
         table.insert_tombstone(ASSIGNED.to_owned(), reader.to_string())?;
         table.insert_tombstone(DISTANCE.to_owned(), reader.to_string())?;
+        if table.contains_key(HEARTBEAT, &reader.to_string()) {
+            table.insert_tombstone(HEARTBEAT.to_owned(), reader.to_string())?;
+        }
         Ok(None)
     }
 
     ///
-    /// Compute the number of segments to acquire.
+    /// Compute the number of segments to acquire. A negative number indicates the number of
+    /// segments the reader should release. The computation depends on the segment assignment
+    /// strategy of the reader group config.
     ///
-    pub async fn compute_segments_to_acquire_or_release(&mut self, reader: &Reader) -> isize {
-        self.sync.fetch_updates().await.expect("should fetch updates");
-        let assigned_segment_map = self.sync.get_inner_map(ASSIGNED);
+    pub async fn compute_segments_to_acquire_or_release(
+        &mut self,
+        reader: &Reader,
+    ) -> Result<isize, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "compute segments to acquire or release".to_owned(),
+        })?;
+        let assigned_segments = self.sync.get_inner_map(ASSIGNED);
+        let unassigned_segments = self.sync.get_inner_map(UNASSIGNED);
+        Ok(
+            match ReaderGroupState::get_segment_assignment_strategy_internal(&self.sync.get_inner_map(CONFIG))
+            {
+                SegmentAssignmentStrategy::SegmentCount => {
+                    ReaderGroupState::compute_by_segment_count_internal(
+                        reader,
+                        &assigned_segments,
+                        &unassigned_segments,
+                    )
+                }
+                SegmentAssignmentStrategy::UnreadBytes => ReaderGroupState::compute_by_unread_bytes_internal(
+                    reader,
+                    &assigned_segments,
+                    &unassigned_segments,
+                    &self.sync.get_inner_map(DISTANCE),
+                ),
+            },
+        )
+    }
+
+    fn compute_by_segment_count_internal(
+        reader: &Reader,
+        assigned_segment_map: &HashMap<String, Value>,
+        unassigned_segment_map: &HashMap<String, Value>,
+    ) -> isize {
         let num_of_readers = assigned_segment_map.len();
         let mut num_assigned_segments = 0;
         for v in assigned_segment_map.values() {
@@ -283,7 +722,7 @@ impl ReaderGroupState {
                 deserialize_from(&v.data).expect("deserialize assigned segments");
             num_assigned_segments += segments.len();
         }
-        let num_of_segments = num_assigned_segments + self.sync.get_inner_map(UNASSIGNED).len();
+        let num_of_segments = num_assigned_segments + unassigned_segment_map.len();
         debug!(
             " number of segments {:?}, number of readers {:?} in reader group state",
             num_of_segments, num_of_readers
@@ -304,9 +743,334 @@ impl ReaderGroupState {
         expected - current
     }
 
+    // A reader that has considerably more bytes left to read than the average releases one of its
+    // segments, a reader that has considerably less acquires one more segment. Segment count based
+    // balancing is used until every online reader has published its distance to tail.
+    fn compute_by_unread_bytes_internal(
+        reader: &Reader,
+        assigned_segment_map: &HashMap<String, Value>,
+        unassigned_segment_map: &HashMap<String, Value>,
+        distance_map: &HashMap<String, Value>,
+    ) -> isize {
+        let mut distances: Vec<u64> = Vec::with_capacity(assigned_segment_map.len());
+        for r in assigned_segment_map.keys() {
+            let distance = distance_map.get(r).map_or(u64::MAX, |v| {
+                deserialize_from(&v.data).expect("deserialize distance to tail")
+            });
+            if distance == u64::MAX {
+                return ReaderGroupState::compute_by_segment_count_internal(
+                    reader,
+                    assigned_segment_map,
+                    unassigned_segment_map,
+                );
+            }
+            distances.push(distance);
+        }
+        let total: u128 = distances.iter().map(|d| *d as u128).sum();
+        let average = total / distances.len() as u128;
+        let tolerance = average / UNREAD_BYTES_TOLERANCE_DIVISOR;
+        let distance: u128 = distance_map.get(&reader.to_string()).map_or(0, |v| {
+            deserialize_from::<u64>(&v.data).expect("deserialize distance to tail") as u128
+        });
+        let owned = assigned_segment_map.get(&reader.to_string()).map_or(0, |v| {
+            let seg: HashMap<ScopedSegment, Offset> =
+                deserialize_from(&v.data).expect("deserialize of assigned segments");
+            seg.len()
+        });
+        let has_unassigned = !unassigned_segment_map.is_empty();
+        debug!(
+            "reader {:?} owns {} segments with distance to tail {}, average distance to tail is {}",
+            reader, owned, distance, average
+        );
+
+        if distance > average + tolerance {
+            // the reader is behind, keep at least one segment.
+            if owned > 1 {
+                -1
+            } else if owned == 0 && has_unassigned {
+                1
+            } else {
+                0
+            }
+        } else if distance + tolerance < average {
+            // the reader is ahead, pick up more work.
+            let count_based = ReaderGroupState::compute_by_segment_count_internal(
+                reader,
+                assigned_segment_map,
+                unassigned_segment_map,
+            );
+            if has_unassigned {
+                count_based.max(1)
+            } else {
+                0
+            }
+        } else {
+            let count_based = ReaderGroupState::compute_by_segment_count_internal(
+                reader,
+                assigned_segment_map,
+                unassigned_segment_map,
+            );
+            if has_unassigned {
+                count_based.max(0)
+            } else {
+                0
+            }
+        }
+    }
+
+    /// Returns the segment assignment strategy of the reader group.
+    pub async fn get_segment_assignment_strategy(
+        &mut self,
+    ) -> Result<SegmentAssignmentStrategy, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "fetch segment assignment strategy".to_owned(),
+        })?;
+        Ok(ReaderGroupState::get_segment_assignment_strategy_internal(
+            &self.sync.get_inner_map(CONFIG),
+        ))
+    }
+
+    fn get_segment_assignment_strategy_internal(
+        config: &HashMap<String, Value>,
+    ) -> SegmentAssignmentStrategy {
+        config
+            .get(DEFAULT_INNER_KEY)
+            .map_or(SegmentAssignmentStrategy::SegmentCount, |v| {
+                let config: ReaderGroupConfigVersioned =
+                    deserialize_from(&v.data).expect("deserialize config");
+                config.get_segment_assignment_strategy()
+            })
+    }
+
+    /// Returns true if all the streams of the reader group are sealed and every segment has been
+    /// read completely, so the readers have nothing left to read.
+    pub async fn is_end_of_stream(&mut self) -> Result<bool, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "check end of stream".to_owned(),
+        })?;
+        Ok(ReaderGroupState::is_end_of_stream_internal(
+            &self.sync.get_inner_map(ASSIGNED),
+            &self.sync.get_inner_map(UNASSIGNED),
+            &self.sync.get_inner_map(FUTURE),
+            &self.sync.get_inner_map(SEALED),
+        ))
+    }
+
+    fn is_end_of_stream_internal(
+        assigned: &HashMap<String, Value>,
+        unassigned: &HashMap<String, Value>,
+        future: &HashMap<String, Value>,
+        sealed: &HashMap<String, Value>,
+    ) -> bool {
+        let nothing_assigned = assigned.values().all(|v| {
+            let segments: HashMap<ScopedSegment, Offset> =
+                deserialize_from(&v.data).expect("deserialize assigned segments");
+            segments.is_empty()
+        });
+        !sealed.is_empty() && nothing_assigned && unassigned.is_empty() && future.is_empty()
+    }
+
+    /// Returns how readers handle segments truncated past their read offsets.
+    pub async fn get_truncation_policy(&mut self) -> Result<TruncationPolicy, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "fetch truncation policy".to_owned(),
+        })?;
+        Ok(self
+            .sync
+            .get_inner_map(CONFIG)
+            .get(DEFAULT_INNER_KEY)
+            .map_or(TruncationPolicy::SkipToHead, |v| {
+                let config: ReaderGroupConfigVersioned =
+                    deserialize_from(&v.data).expect("deserialize config");
+                config.get_truncation_policy()
+            }))
+    }
+
+    /// Returns the stream cuts of all the streams read by the reader group. The stream cuts
+    /// are computed from the latest offsets of the assigned and unassigned segments. Future
+    /// segments are included at offset 0 since their predecessors have partially completed.
+    pub async fn get_stream_cuts(
+        &mut self,
+    ) -> Result<HashMap<ScopedStream, StreamCut>, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "get stream cuts".to_owned(),
+        })?;
+        Ok(ReaderGroupState::get_stream_cuts_internal(
+            self.sync.get_inner_map(ASSIGNED),
+            self.sync.get_inner_map(UNASSIGNED),
+            self.sync.get_inner_map(FUTURE),
+        ))
+    }
+
+    fn get_stream_cuts_internal(
+        assigned_segments: HashMap<String, Value>,
+        unassigned_segments: HashMap<String, Value>,
+        future_segments: HashMap<String, Value>,
+    ) -> HashMap<ScopedStream, StreamCut> {
+        let mut positions: HashMap<ScopedSegment, i64> = HashMap::new();
+        for v in assigned_segments.values() {
+            let segments: HashMap<ScopedSegment, Offset> =
+                deserialize_from(&v.data).expect("deserialize assigned segments");
+            positions.extend(
+                segments
+                    .into_iter()
+                    .map(|(segment, offset)| (segment, offset.read)),
+            );
+        }
+        for (k, v) in unassigned_segments {
+            let offset: Offset = deserialize_from(&v.data).expect("deserialize offset");
+            positions.insert(ScopedSegment::from(k.as_str()), offset.read);
+        }
+        // a future segment only belongs in the cut if none of its pending predecessors are in it,
+        // otherwise the predecessors already cover its key range
+        let mut ready_futures = vec![];
+        for (k, v) in future_segments {
+            let future = ScopedSegment::from(k.as_str());
+            let predecessors: HashSet<Segment> = deserialize_from(&v.data).expect("deserialize hashset");
+            let blocked = predecessors.into_iter().any(|segment| {
+                positions.contains_key(&ScopedSegment {
+                    scope: future.scope.clone(),
+                    stream: future.stream.clone(),
+                    segment,
+                })
+            });
+            if !blocked {
+                ready_futures.push(future);
+            }
+        }
+        for future in ready_futures {
+            positions.entry(future).or_insert(0);
+        }
+
+        let mut stream_cuts: HashMap<ScopedStream, StreamCut> = HashMap::new();
+        for (segment, offset) in positions {
+            let stream = ScopedStream::from(&segment);
+            stream_cuts
+                .entry(stream.clone())
+                .or_insert_with(|| StreamCut::new(stream, HashMap::new()))
+                .segment_offset_map
+                .insert(segment.segment.number, offset);
+        }
+        stream_cuts
+    }
+
+    /// Returns a snapshot of the config, the segments and the end of stream flag of the reader
+    /// group. All the fields are read after a single fetch of the reader group state, so a
+    /// segment that moves between the lists in the meantime is reported exactly once.
+    pub(crate) async fn get_snapshot(&mut self) -> Result<ReaderGroupSnapshot, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "get reader group snapshot".to_owned(),
+        })?;
+        let config =
+            ReaderGroupState::get_config_internal(&self.sync.get_inner_map(CONFIG)).context(SyncError {
+                error_msg: "read reader group config".to_owned(),
+            })?;
+        let assigned = self.sync.get_inner_map(ASSIGNED);
+        let unassigned = self.sync.get_inner_map(UNASSIGNED);
+        let future = self.sync.get_inner_map(FUTURE);
+        let end_of_stream = ReaderGroupState::is_end_of_stream_internal(
+            &assigned,
+            &unassigned,
+            &future,
+            &self.sync.get_inner_map(SEALED),
+        );
+        Ok(ReaderGroupSnapshot {
+            config,
+            assigned: ReaderGroupState::get_assigned_segments_internal(assigned),
+            unassigned: unassigned
+                .into_iter()
+                .map(|(k, v)| {
+                    (
+                        ScopedSegment::from(k.as_str()),
+                        deserialize_from(&v.data).expect("deserialize offset"),
+                    )
+                })
+                .collect(),
+            future: future
+                .into_iter()
+                .map(|(k, v)| {
+                    (
+                        ScopedSegment::from(k.as_str()),
+                        deserialize_from(&v.data).expect("deserialize hashset"),
+                    )
+                })
+                .collect(),
+            end_of_stream,
+        })
+    }
+
+    fn get_assigned_segments_internal(
+        assigned_segments: HashMap<String, Value>,
+    ) -> HashMap<Reader, HashMap<ScopedSegment, Offset>> {
+        assigned_segments
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    Reader::from(k),
+                    deserialize_from(&v.data).expect("deserialize assigned segments"),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the number of bytes each online reader has yet to read from its assigned segments.
+    pub(crate) async fn get_distance_to_tail(
+        &mut self,
+    ) -> Result<HashMap<Reader, u64>, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "get distance to tail".to_owned(),
+        })?;
+        Ok(self
+            .sync
+            .get_inner_map(DISTANCE)
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    Reader::from(k),
+                    deserialize_from(&v.data).expect("deserialize distance to tail"),
+                )
+            })
+            .collect())
+    }
+
+    /// Updates the number of bytes the given readers have yet to read from their assigned segments.
+    pub(crate) async fn update_distance_to_tail(
+        &mut self,
+        distances: HashMap<Reader, u64>,
+    ) -> Result<(), ReaderGroupStateError> {
+        let _res_str = self
+            .sync
+            .insert(|table| ReaderGroupState::update_distance_to_tail_internal(table, &distances))
+            .await
+            .context(SyncError {
+                error_msg: format!("update distance to tail {:?}", distances),
+            })?;
+        Ok(())
+    }
+
+    fn update_distance_to_tail_internal(
+        table: &mut Table,
+        distances: &HashMap<Reader, u64>,
+    ) -> Result<Option<String>, SynchronizerError> {
+        for (reader, distance) in distances {
+            // skip the readers that went offline in the meantime.
+            if table.contains_key(ASSIGNED, &reader.to_string()) {
+                table.insert(
+                    DISTANCE.to_owned(),
+                    reader.to_string(),
+                    "u64".to_owned(),
+                    Box::new(*distance),
+                );
+            }
+        }
+        Ok(None)
+    }
+
     /// Returns the list of all segments.
-    pub async fn get_segments(&mut self) -> HashSet<ScopedSegment> {
-        self.sync.fetch_updates().await.expect("should fetch updates");
+    pub async fn get_segments(&mut self) -> Result<HashSet<ScopedSegment>, ReaderGroupStateError> {
+        self.sync.fetch_updates().await.context(TableMapError {
+            error_msg: "get segments".to_owned(),
+        })?;
 
         let assigned_segments = self.sync.get_inner_map(ASSIGNED);
         let unassigned_segments = self.sync.get_inner_map(UNASSIGNED);
@@ -332,7 +1096,7 @@ impl ReaderGroupState {
                 })
                 .collect::<HashSet<ScopedSegment>>(),
         );
-        set
+        Ok(set)
     }
 
     /// Assigns an unassigned segment to a given reader
@@ -382,7 +1146,9 @@ impl ReaderGroupState {
         &mut self,
         reader: &Reader,
     ) -> Result<HashSet<(ScopedSegment, Offset)>, SynchronizerError> {
-        self.sync.fetch_updates().await.expect("should fetch updates");
+        self.sync.fetch_updates().await.context(SyncTableError {
+            operation: "fetch segments for reader".to_owned(),
+        })?;
         let value =
             self.sync
                 .get(ASSIGNED, &reader.to_string())
@@ -431,12 +1197,17 @@ impl ReaderGroupState {
         let mut assigned_segments = ReaderGroupState::get_reader_owned_segments_from_table(table, reader)?;
         let unassigned_segments = ReaderGroupState::get_unassigned_segments_from_table(table);
 
-        let old_offset = assigned_segments.remove(segment).context(SyncUpdateError {
-            error_msg: format!(
-                "Failed to release segment: should contain only one segment {:?} in assigned list but contains none",
-                segment,
-            )
-        });
+        // the segment is no longer assigned to the reader if its stream has been removed.
+        let old_offset = match assigned_segments.remove(segment) {
+            Some(offset) => offset,
+            None => {
+                info!(
+                    "segment {:?} is not assigned to reader {:?}, ignoring the release",
+                    segment, reader
+                );
+                return Ok(None);
+            }
+        };
         debug!(
             "Removed segment {:?} from assigned segments, the older offset is {:?}",
             segment, old_offset
@@ -474,17 +1245,12 @@ impl ReaderGroupState {
         &mut self,
         reader: &Reader,
         segment_completed: &ScopedSegment,
-        successors_mapped_to_their_predecessors: &im::HashMap<SegmentWithRange, Vec<Segment>>,
+        successors: &StreamSegmentsWithPredecessors,
     ) -> Result<(), ReaderGroupStateError> {
         let _res_str = self
             .sync
             .insert(|table| {
-                ReaderGroupState::segment_completed_internal(
-                    table,
-                    reader,
-                    segment_completed,
-                    successors_mapped_to_their_predecessors,
-                )
+                ReaderGroupState::segment_completed_internal(table, reader, segment_completed, successors)
             })
             .await
             .context(SyncError {
@@ -500,15 +1266,20 @@ impl ReaderGroupState {
         table: &mut Table,
         reader: &Reader,
         segment_completed: &ScopedSegment,
-        successors_mapped_to_their_predecessors: &im::HashMap<SegmentWithRange, Vec<Segment>>,
+        successors: &StreamSegmentsWithPredecessors,
     ) -> Result<Option<String>, SynchronizerError> {
         let mut assigned_segments = ReaderGroupState::get_reader_owned_segments_from_table(table, reader)?;
         let mut future_segments = ReaderGroupState::get_future_segments_from_table(table);
 
-        // remove completed segment from assigned_segment list
-        assigned_segments
-            .remove(segment_completed)
-            .expect("should have assigned this segment to reader");
+        // remove completed segment from assigned_segment list, the segment is no longer assigned
+        // to the reader if its stream has been removed.
+        if assigned_segments.remove(segment_completed).is_none() {
+            info!(
+                "segment {:?} is not assigned to reader {:?}, ignoring its completion",
+                segment_completed, reader
+            );
+            return Ok(None);
+        }
         table.insert(
             ASSIGNED.to_owned(),
             reader.to_string(),
@@ -516,8 +1287,18 @@ impl ReaderGroupState {
             Box::new(assigned_segments),
         );
 
+        // the controller reports the stream as sealed once its last segments have completed.
+        if successors.is_stream_sealed() {
+            table.insert(
+                SEALED.to_owned(),
+                segment_completed.to_string(),
+                "bool".to_owned(),
+                Box::new(true),
+            );
+        }
+
         // add missing successors to future_segments
-        for (segment, list) in successors_mapped_to_their_predecessors {
+        for (segment, list) in &successors.segment_with_predecessors {
             if !future_segments.contains_key(&segment.scoped_segment) {
                 let required_to_complete: HashSet<_> = list.clone().into_iter().collect();
                 table.insert(
@@ -622,6 +1403,28 @@ impl ReaderGroupState {
     }
 }
 
+// The wall clock time used for reader heartbeats.
+fn current_time_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time after unix epoch")
+        .as_millis() as u64
+}
+
+/// A consistent view of the reader group state, see [`ReaderGroupState::get_snapshot`].
+#[derive(Debug, Clone)]
+pub(crate) struct ReaderGroupSnapshot {
+    pub(crate) config: ReaderGroupConfigVersioned,
+    /// The segments and offsets assigned to each online reader.
+    pub(crate) assigned: HashMap<Reader, HashMap<ScopedSegment, Offset>>,
+    /// The segments waiting to be assigned along with their offsets.
+    pub(crate) unassigned: HashMap<ScopedSegment, Offset>,
+    /// The future segments mapped to the predecessors that are yet to be completed.
+    pub(crate) future: HashMap<ScopedSegment, HashSet<Segment>>,
+    /// True if all the streams are sealed and every segment has been read completely.
+    pub(crate) end_of_stream: bool,
+}
+
 #[derive(new, Serialize, Deserialize, PartialEq, Debug, Clone, Hash, Eq)]
 pub struct Offset {
     /// The client has read to this offset and handle the result to the application/caller.
@@ -634,10 +1437,11 @@ pub struct Offset {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::reader_group_config::ReaderGroupConfigV1;
     use crate::table_synchronizer::{serialize, Value};
     use lazy_static::*;
     use ordered_float::OrderedFloat;
-    use pravega_client_shared::{Scope, Segment, Stream};
+    use pravega_client_shared::{Scope, Segment, SegmentWithRange, Stream};
 
     lazy_static! {
         static ref READER: Reader = Reader::from("test".to_owned());
@@ -778,9 +1582,10 @@ mod test {
             &mut table,
             &READER,
             &SEGMENT_TEST,
-            &successors_mapped_to_their_predecessors,
+            &StreamSegmentsWithPredecessors::new(successors_mapped_to_their_predecessors),
         )
         .expect("reader segment completed");
+        assert!(!table.contains_key(SEALED, &SEGMENT_TEST.to_string()));
         assert!(table.contains_key(UNASSIGNED, &successor0.to_string()));
         assert!(table.contains_key(UNASSIGNED, &successor1.to_string()));
 
@@ -813,4 +1618,337 @@ mod test {
 
         assert_eq!(table.get_inner_map(UNASSIGNED).len(), 2);
     }
+
+    #[test]
+    fn test_reader_group_state_reset() {
+        let mut table = set_up();
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+            .expect("assign segment to reader");
+        assert_eq!(
+            ReaderGroupState::get_generation_internal(&table.get_inner_map(GENERATION)),
+            0
+        );
+
+        let mut new_segment = SEGMENT_TEST.clone();
+        new_segment.segment.number = 1;
+        let mut segments = HashMap::new();
+        segments.insert(SEGMENT_TEST.clone(), Offset::new(5));
+        segments.insert(new_segment.clone(), Offset::new(0));
+
+        let config = ReaderGroupConfigVersioned::V1(ReaderGroupConfigV1::new());
+        ReaderGroupState::reset_internal(&mut table, &config, &segments).expect("reset reader group");
+
+        // reader stays online without any assigned segments.
+        let positions =
+            ReaderGroupState::get_reader_positions_internal(&READER, table.get_inner_map(ASSIGNED))
+                .expect("get reader positions");
+        assert!(positions.is_empty());
+        assert_eq!(
+            ReaderGroupState::get_unassigned_segments_from_table(&mut table),
+            segments
+        );
+        assert_eq!(
+            ReaderGroupState::get_generation_internal(&table.get_inner_map(GENERATION)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_remove_all_readers() {
+        let mut table = set_up();
+        let reader2 = Reader::from("test2".to_owned());
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::add_reader_internal(&mut table, &reader2).expect("add reader");
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+            .expect("assign segment to reader");
+
+        ReaderGroupState::remove_all_readers_internal(&mut table).expect("remove all readers");
+
+        assert!(table.get_inner_map(ASSIGNED).is_empty());
+        assert!(table.get_inner_map(DISTANCE).is_empty());
+        assert!(table.contains_key(UNASSIGNED, &SEGMENT_TEST.to_string()));
+    }
+
+    #[test]
+    fn test_get_stream_cuts() {
+        let mut table = set_up();
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+            .expect("assign segment to reader");
+        let mut update = HashMap::new();
+        update.insert(SEGMENT_TEST.clone(), Offset::new(10));
+        ReaderGroupState::update_reader_positions_internal(&mut table, &READER, &update)
+            .expect("update reader position");
+
+        let mut unassigned = SEGMENT_TEST.clone();
+        unassigned.segment.number = 1;
+        table.insert(
+            UNASSIGNED.to_owned(),
+            unassigned.to_string(),
+            "Offset".to_owned(),
+            Box::new(Offset::new(20)),
+        );
+        // segment 2 still waits on segment 1, which is in the cut
+        let mut blocked_future = SEGMENT_TEST.clone();
+        blocked_future.segment.number = 2;
+        let mut predecessors = HashSet::new();
+        predecessors.insert(Segment::from(1));
+        table.insert(
+            FUTURE.to_owned(),
+            blocked_future.to_string(),
+            "HashSet<i64>".to_owned(),
+            Box::new(predecessors),
+        );
+        // segment 3 waits on segment 5, which is not in the cut
+        let mut ready_future = SEGMENT_TEST.clone();
+        ready_future.segment.number = 3;
+        let mut predecessors = HashSet::new();
+        predecessors.insert(Segment::from(5));
+        table.insert(
+            FUTURE.to_owned(),
+            ready_future.to_string(),
+            "HashSet<i64>".to_owned(),
+            Box::new(predecessors),
+        );
+
+        let stream_cuts = ReaderGroupState::get_stream_cuts_internal(
+            table.get_inner_map(ASSIGNED),
+            table.get_inner_map(UNASSIGNED),
+            table.get_inner_map(FUTURE),
+        );
+        let stream = ScopedStream::from(&*SEGMENT_TEST);
+        assert_eq!(stream_cuts.len(), 1);
+        let mut expected = HashMap::new();
+        expected.insert(0, 10);
+        expected.insert(1, 20);
+        expected.insert(3, 0);
+        assert_eq!(
+            stream_cuts.get(&stream).expect("get stream cut"),
+            &StreamCut::new(stream, expected)
+        );
+    }
+
+    #[test]
+    fn test_update_distance_to_tail() {
+        let mut table = set_up();
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+
+        let offline_reader = Reader::from("offline".to_owned());
+        let mut distances = HashMap::new();
+        distances.insert(READER.clone(), 100u64);
+        distances.insert(offline_reader.clone(), 50u64);
+        ReaderGroupState::update_distance_to_tail_internal(&mut table, &distances)
+            .expect("update distance to tail");
+
+        let distance: u64 = deserialize_from(
+            &table
+                .get(DISTANCE, &READER.to_string())
+                .expect("get distance to tail")
+                .data,
+        )
+        .expect("deserialize distance to tail");
+        assert_eq!(distance, 100);
+        assert!(!table.contains_key(DISTANCE, &offline_reader.to_string()));
+    }
+
+    #[test]
+    fn test_compute_by_unread_bytes() {
+        let mut table = set_up();
+        let reader2 = Reader::from("test2".to_owned());
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::add_reader_internal(&mut table, &reader2).expect("add reader");
+        for number in 1..3 {
+            let mut segment = SEGMENT_TEST.clone();
+            segment.segment.number = number;
+            table.insert(
+                UNASSIGNED.to_owned(),
+                segment.to_string(),
+                "Offset".to_owned(),
+                Box::new(Offset::new(0)),
+            );
+        }
+        for _ in 0..3 {
+            ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+                .expect("assign segment to reader");
+        }
+
+        // distance to tail is not published yet, fall back to segment count.
+        assert_eq!(
+            ReaderGroupState::compute_by_unread_bytes_internal(
+                &reader2,
+                &table.get_inner_map(ASSIGNED),
+                &table.get_inner_map(UNASSIGNED),
+                &table.get_inner_map(DISTANCE),
+            ),
+            2
+        );
+
+        let mut distances = HashMap::new();
+        distances.insert(READER.clone(), 1000u64);
+        distances.insert(reader2.clone(), 0u64);
+        ReaderGroupState::update_distance_to_tail_internal(&mut table, &distances)
+            .expect("update distance to tail");
+
+        // the reader that is behind releases a segment.
+        assert_eq!(
+            ReaderGroupState::compute_by_unread_bytes_internal(
+                &READER,
+                &table.get_inner_map(ASSIGNED),
+                &table.get_inner_map(UNASSIGNED),
+                &table.get_inner_map(DISTANCE),
+            ),
+            -1
+        );
+        // nothing to acquire yet for the reader that is ahead.
+        assert_eq!(
+            ReaderGroupState::compute_by_unread_bytes_internal(
+                &reader2,
+                &table.get_inner_map(ASSIGNED),
+                &table.get_inner_map(UNASSIGNED),
+                &table.get_inner_map(DISTANCE),
+            ),
+            0
+        );
+
+        ReaderGroupState::release_segment_internal(&mut table, &READER, &SEGMENT_TEST, &Offset::new(0))
+            .expect("release segment");
+        assert_eq!(
+            ReaderGroupState::compute_by_unread_bytes_internal(
+                &reader2,
+                &table.get_inner_map(ASSIGNED),
+                &table.get_inner_map(UNASSIGNED),
+                &table.get_inner_map(DISTANCE),
+            ),
+            2
+        );
+    }
+
+    #[test]
+    fn test_heartbeat() {
+        let mut table = set_up();
+        let config = ReaderGroupConfigVersioned::V1(ReaderGroupConfigV1::new());
+        let lease = config.get_reader_lease_millis();
+        table.insert(
+            CONFIG.to_owned(),
+            DEFAULT_INNER_KEY.to_owned(),
+            "ReaderGroupConfigVersioned".to_owned(),
+            Box::new(config),
+        );
+        let reader2 = Reader::from("test2".to_owned());
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::add_reader_internal(&mut table, &reader2).expect("add reader");
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &reader2)
+            .expect("assign segment to reader");
+        let mut update = HashMap::new();
+        update.insert(SEGMENT_TEST.clone(), Offset::new(10));
+        ReaderGroupState::update_reader_positions_internal(&mut table, &reader2, &update)
+            .expect("update reader position");
+
+        // both readers are within the lease.
+        let now = current_time_millis();
+        ReaderGroupState::heartbeat_internal(&mut table, &READER, now).expect("heartbeat");
+        assert_eq!(table.get_inner_map(ASSIGNED).len(), 2);
+
+        // reader2 has not sent a heartbeat within the lease.
+        ReaderGroupState::heartbeat_internal(&mut table, &READER, now + lease + 1000).expect("heartbeat");
+        let online_readers = ReaderGroupState::get_online_readers_internal(table.get_inner_map(ASSIGNED));
+        assert_eq!(online_readers, vec![READER.clone()]);
+        assert!(!table.contains_key(HEARTBEAT, &reader2.to_string()));
+        assert_eq!(
+            ReaderGroupState::get_unassigned_segments_from_table(&mut table)
+                .get(&SEGMENT_TEST)
+                .expect("segment is unassigned"),
+            &Offset::new(10)
+        );
+
+        // an offline reader cannot send heartbeats.
+        assert!(ReaderGroupState::heartbeat_internal(&mut table, &reader2, now).is_err());
+    }
+
+    #[test]
+    fn test_add_and_remove_stream() {
+        let mut table = set_up();
+        let stream = ScopedStream::from("scope/other");
+        let stream_segment = ScopedSegment::from("scope/other/0.#epoch.0");
+        let mut config = ReaderGroupConfigVersioned::V1(ReaderGroupConfigV1::new());
+        config.add_stream(ScopedStream::from(&*SEGMENT_TEST), StreamCutVersioned::UNBOUNDED);
+        table.insert(
+            CONFIG.to_owned(),
+            DEFAULT_INNER_KEY.to_owned(),
+            "ReaderGroupConfigVersioned".to_owned(),
+            Box::new(config),
+        );
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+            .expect("assign segment to reader");
+
+        // the stream is added to the config and its segments become unassigned.
+        let mut segments = HashMap::new();
+        segments.insert(stream_segment.clone(), Offset::new(5));
+        ReaderGroupState::add_stream_internal(&mut table, &stream, &StreamCutVersioned::UNBOUNDED, &segments)
+            .expect("add stream");
+        let unassigned = ReaderGroupState::get_unassigned_segments_from_table(&mut table);
+        assert_eq!(unassigned.get(&stream_segment), Some(&Offset::new(5)));
+        let config = ReaderGroupState::get_config_internal(&table.get_inner_map(CONFIG)).expect("get config");
+        assert!(config.contains_stream(&stream));
+
+        // a stream cannot be added twice.
+        assert!(ReaderGroupState::add_stream_internal(
+            &mut table,
+            &stream,
+            &StreamCutVersioned::UNBOUNDED,
+            &segments
+        )
+        .is_err());
+
+        // the segments of the removed stream are dropped, the reader keeps the other segments.
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+            .expect("assign segment to reader");
+        ReaderGroupState::remove_stream_internal(&mut table, &stream).expect("remove stream");
+        let config = ReaderGroupState::get_config_internal(&table.get_inner_map(CONFIG)).expect("get config");
+        assert!(!config.contains_stream(&stream));
+        assert!(ReaderGroupState::get_unassigned_segments_from_table(&mut table).is_empty());
+        let owned = ReaderGroupState::get_reader_owned_segments_from_table(&mut table, &READER)
+            .expect("get reader owned segments");
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned.get(&SEGMENT_TEST), Some(&Offset::new(0)));
+        assert_eq!(
+            ReaderGroupState::get_generation_internal(&table.get_inner_map(GENERATION)),
+            0
+        );
+
+        // removing a stream which is not read by the reader group is a no-op.
+        ReaderGroupState::remove_stream_internal(&mut table, &stream).expect("remove stream");
+    }
+
+    #[test]
+    fn test_end_of_stream() {
+        let mut table = set_up();
+        ReaderGroupState::add_reader_internal(&mut table, &READER).expect("add reader");
+        ReaderGroupState::assign_segment_to_reader_internal(&mut table, &READER)
+            .expect("assign segment to reader");
+        assert!(!ReaderGroupState::is_end_of_stream_internal(
+            &table.get_inner_map(ASSIGNED),
+            &table.get_inner_map(UNASSIGNED),
+            &table.get_inner_map(FUTURE),
+            &table.get_inner_map(SEALED),
+        ));
+
+        // the only segment completes without successors, the stream is sealed.
+        ReaderGroupState::segment_completed_internal(
+            &mut table,
+            &READER,
+            &SEGMENT_TEST,
+            &StreamSegmentsWithPredecessors::new(im::HashMap::new()),
+        )
+        .expect("segment completed");
+        assert!(table.contains_key(SEALED, &SEGMENT_TEST.to_string()));
+        assert!(ReaderGroupState::is_end_of_stream_internal(
+            &table.get_inner_map(ASSIGNED),
+            &table.get_inner_map(UNASSIGNED),
+            &table.get_inner_map(FUTURE),
+            &table.get_inner_map(SEALED),
+        ));
+    }
 }
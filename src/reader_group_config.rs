@@ -15,6 +15,7 @@ use serde_cbor::from_slice;
 use serde_cbor::to_vec;
 use snafu::ResultExt;
 use std::collections::HashMap;
+use std::time::Duration;
 
 ///
 /// Specifies the ReaderGroupConfig.
@@ -34,6 +35,10 @@ impl ReaderGroupConfig {
             group_refresh_time_millis,
             starting_stream_cuts: HashMap::new(),
             ending_stream_cuts: HashMap::new(),
+            segment_assignment_strategy: SegmentAssignmentStrategy::SegmentCount,
+            reader_lease_millis: DEFAULT_READER_LEASE_MILLIS,
+            truncation_policy: TruncationPolicy::SkipToHead,
+            reader_config: ReaderConfig::default(),
         };
         ReaderGroupConfig {
             config: ReaderGroupConfigVersioned::V1(conf_v1),
@@ -65,11 +70,251 @@ impl ReaderGroupConfig {
             .cloned()
             .collect::<Vec<ScopedStream>>()
     }
+
+    ///
+    /// Method to obtain the strategy used to distribute segments among the readers.
+    ///
+    pub fn get_segment_assignment_strategy(&self) -> SegmentAssignmentStrategy {
+        self.config.get_segment_assignment_strategy()
+    }
+
+    ///
+    /// Method to obtain how readers handle segments truncated past their read offsets.
+    ///
+    pub fn get_truncation_policy(&self) -> TruncationPolicy {
+        self.config.get_truncation_policy()
+    }
+
+    ///
+    /// Method to obtain the ReaderConfig used by the readers of the reader group.
+    ///
+    pub fn get_reader_config(&self) -> ReaderConfig {
+        let ReaderGroupConfigVersioned::V1(v1) = &self.config;
+        v1.reader_config.clone()
+    }
+
+    ///
+    /// Method to obtain the starting stream cuts of the streams in a ReaderGroupConfig.
+    ///
+    pub(crate) fn get_starting_stream_cuts(&self) -> HashMap<ScopedStream, StreamCutVersioned> {
+        let ReaderGroupConfigVersioned::V1(v1) = &self.config;
+        v1.starting_stream_cuts.clone()
+    }
+}
+
+///
+/// Specifies how the segments of a reader group are distributed among its readers.
+///
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SegmentAssignmentStrategy {
+    /// Every reader owns roughly the same number of segments.
+    SegmentCount,
+    /// Segments move from the readers that have the most bytes left to read to the readers
+    /// that have the least, based on the distance to tail published by each reader.
+    UnreadBytes,
+}
+
+///
+/// Specifies how a reader handles a segment which was truncated past the offset it is reading from.
+///
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TruncationPolicy {
+    /// The reader skips ahead to the current head of the segment, the skipped bytes are logged
+    /// and counted.
+    SkipToHead,
+    /// The reader stops reading from the segment and returns an error. The segment stays assigned
+    /// to the reader, which keeps returning the error until the reader group is reset.
+    Fail,
+}
+
+///
+/// Specifies where the readers of a reader group start reading the streams of the reader group.
+///
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum StartPosition {
+    /// The readers read all the events of the streams starting at their heads.
+    Head,
+    /// The readers only read the events written after the reader group is created. The tail
+    /// offsets of the streams are resolved when the reader group is created.
+    Tail,
+}
+
+// Used when deserializing a config that was persisted before the strategy was introduced.
+fn default_segment_assignment_strategy() -> SegmentAssignmentStrategy {
+    SegmentAssignmentStrategy::SegmentCount
+}
+
+// Used when deserializing a config that was persisted before the reader lease was introduced.
+fn default_reader_lease_millis() -> u64 {
+    DEFAULT_READER_LEASE_MILLIS
+}
+
+// Used when deserializing a config that was persisted before the truncation policy was introduced.
+fn default_truncation_policy() -> TruncationPolicy {
+    TruncationPolicy::SkipToHead
+}
+
+const DEFAULT_READER_LEASE_MILLIS: u64 = 60000;
+
+const DEFAULT_REBALANCE_INTERVAL_MILLIS: u64 = 10000;
+const DEFAULT_ACQUIRE_TIMEOUT_MILLIS: u64 = 1000;
+const DEFAULT_READ_BUFFER_SIZE: i32 = 8 * 1024 * 1024; // max size for a single Event
+const DEFAULT_MAX_PREFETCH_BYTES: usize = 8 * DEFAULT_READ_BUFFER_SIZE as usize;
+
+///
+/// Specifies the timing and buffering of an EventReader.
+/// ReaderConfig::default() rebalances segments every 10 seconds, waits at most 1 second for data
+/// in `acquire_segment`, reads 8MB per request and prefetches at most 64MB.
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct ReaderConfig {
+    rebalance_interval_millis: u64,
+    acquire_timeout_millis: u64,
+    read_buffer_size: i32,
+    max_prefetch_bytes: usize,
+    #[serde(default)]
+    ack_tracking: bool,
+}
+
+impl Default for ReaderConfig {
+    fn default() -> Self {
+        ReaderConfigBuilder::default().build()
+    }
+}
+
+impl ReaderConfig {
+    ///
+    /// The interval at which the reader rebalances segments with the other readers.
+    ///
+    pub fn get_rebalance_interval(&self) -> Duration {
+        Duration::from_millis(self.rebalance_interval_millis)
+    }
+
+    ///
+    /// The maximum time `acquire_segment` waits for data.
+    ///
+    pub fn get_acquire_timeout(&self) -> Duration {
+        Duration::from_millis(self.acquire_timeout_millis)
+    }
+
+    ///
+    /// The number of bytes requested from the segment store by a single read.
+    ///
+    pub fn get_read_buffer_size(&self) -> i32 {
+        self.read_buffer_size
+    }
+
+    ///
+    /// The maximum number of bytes the reader prefetches from its segments before they are
+    /// acquired by the application.
+    ///
+    pub fn get_max_prefetch_bytes(&self) -> usize {
+        self.max_prefetch_bytes
+    }
+
+    ///
+    /// Whether every event read must be acknowledged before its offset is published.
+    ///
+    pub fn is_ack_tracking_enabled(&self) -> bool {
+        self.ack_tracking
+    }
+}
+
+pub struct ReaderConfigBuilder {
+    rebalance_interval_millis: u64,
+    acquire_timeout_millis: u64,
+    read_buffer_size: i32,
+    max_prefetch_bytes: usize,
+    ack_tracking: bool,
+}
+
+impl Default for ReaderConfigBuilder {
+    fn default() -> Self {
+        Self {
+            rebalance_interval_millis: DEFAULT_REBALANCE_INTERVAL_MILLIS,
+            acquire_timeout_millis: DEFAULT_ACQUIRE_TIMEOUT_MILLIS,
+            read_buffer_size: DEFAULT_READ_BUFFER_SIZE,
+            max_prefetch_bytes: DEFAULT_MAX_PREFETCH_BYTES,
+            ack_tracking: false,
+        }
+    }
+}
+
+impl ReaderConfigBuilder {
+    ///
+    /// Set the interval at which the reader rebalances segments with the other readers.
+    ///
+    pub fn set_rebalance_interval(&mut self, rebalance_interval_millis: u64) -> &mut Self {
+        self.rebalance_interval_millis = rebalance_interval_millis;
+        self
+    }
+
+    ///
+    /// Set the maximum time `acquire_segment` waits for data.
+    ///
+    pub fn set_acquire_timeout(&mut self, acquire_timeout_millis: u64) -> &mut Self {
+        self.acquire_timeout_millis = acquire_timeout_millis;
+        self
+    }
+
+    ///
+    /// Set the number of bytes requested from the segment store by a single read.
+    ///
+    pub fn set_read_buffer_size(&mut self, read_buffer_size: i32) -> &mut Self {
+        self.read_buffer_size = read_buffer_size;
+        self
+    }
+
+    ///
+    /// Set the maximum number of bytes the reader prefetches from its segments. A reader reading
+    /// from many segments waits for the application to consume data once this limit is reached.
+    ///
+    pub fn set_max_prefetch_bytes(&mut self, max_prefetch_bytes: usize) -> &mut Self {
+        self.max_prefetch_bytes = max_prefetch_bytes;
+        self
+    }
+
+    ///
+    /// Enable acknowledgement of events. Every event returned by a segment slice then carries an
+    /// [`AckHandle`] and the reader only publishes the offset of the lowest un-acknowledged event
+    /// of a segment when it releases the segment or goes offline, so events which have not been
+    /// processed are read again after a crash.
+    ///
+    /// [`AckHandle`]: crate::ack_tracker::AckHandle
+    ///
+    pub fn set_ack_tracking(&mut self, ack_tracking: bool) -> &mut Self {
+        self.ack_tracking = ack_tracking;
+        self
+    }
+
+    ///
+    /// Build a ReaderConfig object.
+    /// This method panics for invalid configuration.
+    ///
+    pub fn build(&self) -> ReaderConfig {
+        assert!(self.read_buffer_size > 0, "Read buffer size should be positive");
+        assert!(
+            self.max_prefetch_bytes >= self.read_buffer_size as usize,
+            "Prefetch limit should be at least the read buffer size"
+        );
+        ReaderConfig {
+            rebalance_interval_millis: self.rebalance_interval_millis,
+            acquire_timeout_millis: self.acquire_timeout_millis,
+            read_buffer_size: self.read_buffer_size,
+            max_prefetch_bytes: self.max_prefetch_bytes,
+            ack_tracking: self.ack_tracking,
+        }
+    }
 }
 
 pub struct ReaderGroupConfigBuilder {
     group_refresh_time_millis: u64,
     starting_stream_cuts: HashMap<ScopedStream, StreamCutVersioned>,
+    segment_assignment_strategy: SegmentAssignmentStrategy,
+    reader_lease_millis: u64,
+    truncation_policy: TruncationPolicy,
+    reader_config: ReaderConfig,
+    start_position: StartPosition,
 }
 
 impl Default for ReaderGroupConfigBuilder {
@@ -77,6 +322,11 @@ impl Default for ReaderGroupConfigBuilder {
         Self {
             group_refresh_time_millis: 3000,
             starting_stream_cuts: Default::default(),
+            segment_assignment_strategy: SegmentAssignmentStrategy::SegmentCount,
+            reader_lease_millis: DEFAULT_READER_LEASE_MILLIS,
+            truncation_policy: TruncationPolicy::SkipToHead,
+            reader_config: ReaderConfig::default(),
+            start_position: StartPosition::Head,
         }
     }
 }
@@ -90,6 +340,52 @@ impl ReaderGroupConfigBuilder {
         self
     }
 
+    ///
+    /// Set the strategy used to distribute segments among the readers.
+    ///
+    pub fn set_segment_assignment_strategy(&mut self, strategy: SegmentAssignmentStrategy) -> &mut Self {
+        self.segment_assignment_strategy = strategy;
+        self
+    }
+
+    ///
+    /// Set the time after which a reader that has not sent a heartbeat is considered dead.
+    /// Its segments are then returned to the reader group at the last published offsets.
+    /// Readers send heartbeats at most once every rebalance interval while acquiring and
+    /// releasing segments, so the lease should be comfortably larger than both the rebalance
+    /// interval and the time taken to process a segment slice. A reader which has been put
+    /// offline drops its segments and fails to acquire new ones.
+    ///
+    pub fn set_reader_lease_time(&mut self, reader_lease_millis: u64) -> &mut Self {
+        self.reader_lease_millis = reader_lease_millis;
+        self
+    }
+
+    ///
+    /// Set how readers handle segments which are truncated past their read offsets.
+    ///
+    pub fn set_truncation_policy(&mut self, policy: TruncationPolicy) -> &mut Self {
+        self.truncation_policy = policy;
+        self
+    }
+
+    ///
+    /// Set the ReaderConfig used by the readers of the reader group.
+    ///
+    pub fn set_reader_config(&mut self, reader_config: ReaderConfig) -> &mut Self {
+        self.reader_config = reader_config;
+        self
+    }
+
+    ///
+    /// Set where the readers start reading the streams of the reader group, by default they
+    /// start at the head of the streams.
+    ///
+    pub fn set_start_position(&mut self, start_position: StartPosition) -> &mut Self {
+        self.start_position = start_position;
+        self
+    }
+
     ///
     /// Add a Pravega Stream to the reader group.
     ///
@@ -108,11 +404,23 @@ impl ReaderGroupConfigBuilder {
             !self.starting_stream_cuts.is_empty(),
             "Atleast 1 stream should be part of the reader group config"
         );
+        let starting_stream_cuts = match self.start_position {
+            StartPosition::Head => self.starting_stream_cuts.clone(),
+            StartPosition::Tail => self
+                .starting_stream_cuts
+                .keys()
+                .map(|stream| (stream.clone(), StreamCutVersioned::Tail))
+                .collect(),
+        };
         ReaderGroupConfig {
             config: ReaderGroupConfigVersioned::V1(ReaderGroupConfigV1 {
                 group_refresh_time_millis: self.group_refresh_time_millis,
-                starting_stream_cuts: self.starting_stream_cuts.clone(),
+                starting_stream_cuts,
                 ending_stream_cuts: Default::default(), // This will be extended when bounded processing is enabled.
+                segment_assignment_strategy: self.segment_assignment_strategy,
+                reader_lease_millis: self.reader_lease_millis,
+                truncation_policy: self.truncation_policy,
+                reader_config: self.reader_config.clone(),
             }),
         }
     }
@@ -138,6 +446,54 @@ impl ReaderGroupConfigVersioned {
         })?;
         Ok(decoded)
     }
+
+    pub(crate) fn get_segment_assignment_strategy(&self) -> SegmentAssignmentStrategy {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.segment_assignment_strategy
+    }
+
+    pub(crate) fn get_group_refresh_time_millis(&self) -> u64 {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.group_refresh_time_millis
+    }
+
+    pub(crate) fn get_reader_lease_millis(&self) -> u64 {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.reader_lease_millis
+    }
+
+    pub(crate) fn get_truncation_policy(&self) -> TruncationPolicy {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.truncation_policy
+    }
+
+    pub(crate) fn add_stream(&mut self, stream: ScopedStream, starting_stream_cut: StreamCutVersioned) {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.starting_stream_cuts
+            .insert(stream.clone(), starting_stream_cut);
+        v1.ending_stream_cuts
+            .insert(stream, StreamCutVersioned::UNBOUNDED);
+    }
+
+    pub(crate) fn set_starting_stream_cut(
+        &mut self,
+        stream: ScopedStream,
+        starting_stream_cut: StreamCutVersioned,
+    ) {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.starting_stream_cuts.insert(stream, starting_stream_cut);
+    }
+
+    pub(crate) fn contains_stream(&self, stream: &ScopedStream) -> bool {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.starting_stream_cuts.contains_key(stream)
+    }
+
+    pub(crate) fn remove_stream(&mut self, stream: &ScopedStream) {
+        let ReaderGroupConfigVersioned::V1(v1) = self;
+        v1.starting_stream_cuts.remove(stream);
+        v1.ending_stream_cuts.remove(stream);
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
@@ -147,6 +503,18 @@ pub(crate) struct ReaderGroupConfigV1 {
     group_refresh_time_millis: u64,
     starting_stream_cuts: HashMap<ScopedStream, StreamCutVersioned>,
     ending_stream_cuts: HashMap<ScopedStream, StreamCutVersioned>,
+    /// how segments are distributed among the readers.
+    #[serde(default = "default_segment_assignment_strategy")]
+    segment_assignment_strategy: SegmentAssignmentStrategy,
+    /// time after which a reader without heartbeats is considered dead.
+    #[serde(default = "default_reader_lease_millis")]
+    reader_lease_millis: u64,
+    /// how readers handle truncated segments.
+    #[serde(default = "default_truncation_policy")]
+    truncation_policy: TruncationPolicy,
+    /// timing and buffering of the readers.
+    #[serde(default)]
+    reader_config: ReaderConfig,
 }
 
 impl Default for ReaderGroupConfigV1 {
@@ -161,6 +529,10 @@ impl ReaderGroupConfigV1 {
             group_refresh_time_millis: 3000,
             starting_stream_cuts: HashMap::new(),
             ending_stream_cuts: HashMap::new(),
+            segment_assignment_strategy: SegmentAssignmentStrategy::SegmentCount,
+            reader_lease_millis: DEFAULT_READER_LEASE_MILLIS,
+            truncation_policy: TruncationPolicy::SkipToHead,
+            reader_config: ReaderConfig::default(),
         }
     }
 
@@ -221,11 +593,35 @@ mod tests {
     fn test_reader_group_config_builder() {
         let rg_config = ReaderGroupConfigBuilder::default()
             .set_group_refresh_time(4000)
+            .set_segment_assignment_strategy(SegmentAssignmentStrategy::UnreadBytes)
+            .set_reader_lease_time(10000)
+            .set_truncation_policy(TruncationPolicy::Fail)
+            .set_reader_config(
+                ReaderConfigBuilder::default()
+                    .set_rebalance_interval(5000)
+                    .set_acquire_timeout(200)
+                    .set_read_buffer_size(1024)
+                    .set_max_prefetch_bytes(4096)
+                    .build(),
+            )
             .add_stream(ScopedStream::from("scope1/s1"))
             .add_stream(ScopedStream::from("scope2/s2"))
             .build();
         let ReaderGroupConfigVersioned::V1(v1) = rg_config.config;
         assert_eq!(v1.group_refresh_time_millis, 4000);
+        assert_eq!(v1.reader_lease_millis, 10000);
+        assert_eq!(v1.truncation_policy, TruncationPolicy::Fail);
+        assert_eq!(
+            v1.reader_config.get_rebalance_interval(),
+            Duration::from_millis(5000)
+        );
+        assert_eq!(v1.reader_config.get_acquire_timeout(), Duration::from_millis(200));
+        assert_eq!(v1.reader_config.get_read_buffer_size(), 1024);
+        assert_eq!(v1.reader_config.get_max_prefetch_bytes(), 4096);
+        assert_eq!(
+            v1.segment_assignment_strategy,
+            SegmentAssignmentStrategy::UnreadBytes
+        );
         //Validate both the streams are present.
         assert!(v1
             .starting_stream_cuts
@@ -246,6 +642,22 @@ mod tests {
         let ReaderGroupConfigVersioned::V1(v1) = rg_config.config;
         // verify default
         assert_eq!(v1.group_refresh_time_millis, 3000);
+        assert_eq!(
+            v1.segment_assignment_strategy,
+            SegmentAssignmentStrategy::SegmentCount
+        );
+        assert_eq!(v1.reader_lease_millis, DEFAULT_READER_LEASE_MILLIS);
+        assert_eq!(v1.truncation_policy, TruncationPolicy::SkipToHead);
+        assert_eq!(v1.reader_config.get_rebalance_interval(), Duration::from_secs(10));
+        assert_eq!(
+            v1.reader_config.get_acquire_timeout(),
+            Duration::from_millis(1000)
+        );
+        assert_eq!(v1.reader_config.get_read_buffer_size(), DEFAULT_READ_BUFFER_SIZE);
+        assert_eq!(
+            v1.reader_config.get_max_prefetch_bytes(),
+            DEFAULT_MAX_PREFETCH_BYTES
+        );
         //Validate both the streams are present.
         assert!(v1
             .starting_stream_cuts
@@ -260,4 +672,13 @@ mod tests {
     fn test_reader_group_config_builder_invalid() {
         let _rg_config = ReaderGroupConfigBuilder::default().build();
     }
+
+    #[test]
+    #[should_panic]
+    fn test_reader_config_builder_invalid() {
+        let _reader_config = ReaderConfigBuilder::default()
+            .set_read_buffer_size(1024)
+            .set_max_prefetch_bytes(512)
+            .build();
+    }
 }
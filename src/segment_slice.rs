@@ -8,28 +8,98 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 //
 
+use crate::ack_tracker::{AckHandle, AckTracker};
 use crate::client_factory::ClientFactory;
-use crate::event_reader::SegmentReadResult;
+use crate::event_reader::SegmentReadResultWithEpoch;
 use crate::segment_reader::AsyncSegmentReader;
 use crate::segment_reader::ReaderError::SegmentSealed;
-use bytes::{Buf, BufMut, BytesMut};
+use crate::stream::event_pointer::{EventPointerV1, EventPointerVersioned};
+use crate::stream::position::{PositionV1, PositionVersioned};
+use bytes::{Buf, Bytes, BytesMut};
 use core::fmt;
 use pravega_client_retry::retry_result::Retryable;
 use pravega_client_shared::ScopedSegment;
 use pravega_wire_protocol::commands::{Command, EventCommand, TYPE_PLUS_LENGTH_SIZE};
+use std::cmp;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::error::TryRecvError;
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
+// The time to wait before reading again at the tail of a segment after an empty reply.
+const MIN_EMPTY_READ_BACKOFF: Duration = Duration::from_millis(1);
+const MAX_EMPTY_READ_BACKOFF: Duration = Duration::from_millis(100);
+
 ///
 /// This represents an event that was read from a Pravega Segment and the offset at which the event
-/// was read from.
+/// was read from. The event pointer can be used to fetch this event again, see [`Event::get_position`]
+/// for the position of the event.
+/// The ack handle is only present if ack tracking is enabled in the ReaderConfig.
 ///
 #[derive(Debug)]
 pub struct Event {
     pub offset_in_segment: i64,
     pub value: Vec<u8>,
+    pub event_pointer: EventPointerVersioned,
+    pub ack_handle: Option<AckHandle>,
+}
+
+impl Event {
+    ///
+    /// Return the position right after this event in its segment, reading resumes from there.
+    ///
+    pub fn get_position(&self) -> PositionVersioned {
+        position_after(&self.event_pointer)
+    }
+}
+
+///
+/// This is the same as an Event except that the value is a view into the data read from the
+/// Pravega Segment instead of a copy of it, see [`SegmentSlice::next_bytes`].
+///
+/// [`SegmentSlice::next_bytes`]: SegmentSlice::next_bytes
+///
+#[derive(Debug)]
+pub struct EventBytes {
+    pub offset_in_segment: i64,
+    pub value: Bytes,
+    pub event_pointer: EventPointerVersioned,
+    pub ack_handle: Option<AckHandle>,
+}
+
+impl EventBytes {
+    ///
+    /// Return the position right after this event in its segment, reading resumes from there.
+    ///
+    pub fn get_position(&self) -> PositionVersioned {
+        position_after(&self.event_pointer)
+    }
+}
+
+// The position is only built when it is asked for, so that reading an event does not allocate it.
+fn position_after(event_pointer: &EventPointerVersioned) -> PositionVersioned {
+    let mut offsets = HashMap::with_capacity(1);
+    offsets.insert(
+        event_pointer.get_segment().clone(),
+        event_pointer.get_event_start_offset() + event_pointer.get_event_length() as i64,
+    );
+    PositionVersioned::V1(PositionV1::from_offsets(offsets))
+}
+
+impl From<EventBytes> for Event {
+    fn from(event: EventBytes) -> Self {
+        Event {
+            offset_in_segment: event.offset_in_segment,
+            value: event.value.to_vec(),
+            event_pointer: event.event_pointer,
+            ack_handle: event.ack_handle,
+        }
+    }
 }
 
 ///
@@ -39,6 +109,7 @@ pub struct Event {
 pub struct SegmentSlice {
     pub meta: SliceMetadata,
     pub(crate) slice_return_tx: Option<oneshot::Sender<Option<SliceMetadata>>>,
+    pub(crate) ack_tracker: Option<Arc<AckTracker>>,
 }
 
 impl fmt::Debug for SegmentSlice {
@@ -59,6 +130,8 @@ pub struct SliceMetadata {
     pub end_offset: i64,
     pub(crate) segment_data: SegmentDataBuffer,
     pub partial_data_present: bool,
+    // the parsed scoped_segment, it is set once the first event of the segment is read.
+    pub(crate) parsed_segment: Option<ScopedSegment>,
 }
 
 impl fmt::Debug for SliceMetadata {
@@ -74,11 +147,6 @@ impl fmt::Debug for SliceMetadata {
     }
 }
 
-///
-/// Read Buffer size
-///
-const READ_BUFFER_SIZE: i32 = 8 * 1024 * 1024; // max size for a single Event
-
 ///
 /// Structure to track the offset and byte array.
 ///
@@ -178,6 +246,13 @@ impl SliceMetadata {
     pub fn has_events(&self) -> bool {
         !self.partial_data_present && self.segment_data.value.len() > TYPE_PLUS_LENGTH_SIZE as usize
     }
+
+    // Return the segment of the slice, it is parsed from its name only once.
+    pub(crate) fn get_scoped_segment(&mut self) -> &ScopedSegment {
+        let scoped_segment = &self.scoped_segment;
+        self.parsed_segment
+            .get_or_insert_with(|| ScopedSegment::from(scoped_segment.as_str()))
+    }
 }
 
 impl Default for SliceMetadata {
@@ -190,6 +265,7 @@ impl Default for SliceMetadata {
             end_offset: i64::MAX,
             segment_data: SegmentDataBuffer::empty(),
             partial_data_present: false,
+            parsed_segment: None,
         }
     }
 }
@@ -199,6 +275,7 @@ impl Default for SegmentSlice {
         SegmentSlice {
             meta: Default::default(),
             slice_return_tx: None,
+            ack_tracker: None,
         }
     }
 }
@@ -206,7 +283,8 @@ impl Default for SegmentSlice {
 impl SegmentSlice {
     ///
     /// Create a new SegmentSlice for a given start_offset, segment.
-    /// This spawns an asynchronous task to fetch data from the segment with length of  `READ_BUFFER_SIZE`.
+    /// This spawns an asynchronous task to fetch data from the segment with length of the read buffer size
+    /// of the ReaderConfig.
     /// The channel buffer size is 1 which ensure only one outstanding read request to Segment store.
     ///
     pub(crate) fn new(
@@ -223,36 +301,62 @@ impl SegmentSlice {
                 end_offset: i64::MAX,
                 segment_data: SegmentDataBuffer::empty(),
                 partial_data_present: false,
+                parsed_segment: Some(segment),
             },
             slice_return_tx: Some(slice_return_tx),
+            ack_tracker: None,
         }
     }
 
     ///
     /// Method to fetch data from the Segment store from a given start offset.
-    ///
+    /// Before every read the task acquires `read_buffer_size` bytes from the prefetch limit of
+    /// the reader. The bytes which are not used by the read are returned right away, the rest is
+    /// returned by the reader once it receives the data.
+    /// Reads at the tail of the segment are held by the segment store until data arrives, the
+    /// empty replies of the reads which expire are not sent to the reader.
+    /// The results are tagged with the given read epoch, so that the reader can drop the reads
+    /// which are in flight once the task is stopped.
+    ///
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn get_segment_data(
         segment: ScopedSegment,
         start_offset: i64,
-        tx: Sender<SegmentReadResult>,
+        read_epoch: u64,
+        tx: Sender<SegmentReadResultWithEpoch>,
         mut drop_fetch: oneshot::Receiver<()>,
         factory: ClientFactory,
+        read_buffer_size: i32,
+        prefetch_limit: Arc<Semaphore>,
     ) {
         let mut offset: i64 = start_offset;
+        let mut empty_read_backoff = MIN_EMPTY_READ_BACKOFF;
         let segment_reader = factory.create_async_event_reader(segment.clone()).await;
         loop {
             if let Ok(_) | Err(TryRecvError::Closed) = drop_fetch.try_recv() {
                 info!("Stop reading from the segment");
                 break;
             }
+            // wait until the reader has consumed enough of the prefetched data.
+            tokio::select! {
+                permit = prefetch_limit.acquire_many(read_buffer_size as u32) => {
+                    permit.expect("prefetch limit is never closed").forget();
+                }
+                _ = &mut drop_fetch => {
+                    info!("Stop reading from the segment");
+                    break;
+                }
+            }
             debug!(
                 "Send read request to Segment store at offset {:?} with length {:?}",
-                offset, READ_BUFFER_SIZE
+                offset, read_buffer_size
             );
-            let read = segment_reader.read(offset, READ_BUFFER_SIZE).await;
+            let read = segment_reader.read(offset, read_buffer_size).await;
             match read {
                 Ok(reply) => {
                     let len = reply.data.len();
+                    // return the bytes which are not used by this read.
+                    prefetch_limit.add_permits((read_buffer_size as usize).saturating_sub(len));
                     if len == 0 && reply.end_of_segment {
                         info!("Reached end of segment {:?} during read ", segment.clone());
                         let data = SegmentSealed {
@@ -262,13 +366,29 @@ impl SegmentSlice {
                             error_msg: "reached the end of stream".to_string(),
                         };
                         // send data: this waits until there is capacity in the channel.
-                        if let Err(e) = tx.send(Err((data, offset))).await {
+                        if let Err(e) = tx.send((read_epoch, Err((data, offset)))).await {
                             warn!("Error while sending segment data to event parser {:?} ", e);
                             break;
                         }
                         drop(tx);
                         break;
+                    } else if len == 0 {
+                        // the read at the tail expired without data, issue it again. Back off in
+                        // case the segment store does not hold the reads at the tail.
+                        debug!(
+                            "no data at offset {:?} of segment {:?}, reading again in {:?}",
+                            offset, segment, empty_read_backoff
+                        );
+                        tokio::select! {
+                            _ = sleep(empty_read_backoff) => {}
+                            _ = &mut drop_fetch => {
+                                info!("Stop reading from the segment");
+                                break;
+                            }
+                        }
+                        empty_read_backoff = cmp::min(empty_read_backoff * 2, MAX_EMPTY_READ_BACKOFF);
                     } else {
+                        empty_read_backoff = MIN_EMPTY_READ_BACKOFF;
                         let segment_data = bytes::BytesMut::from(reply.data.as_slice());
                         let data = SegmentDataBuffer {
                             segment: segment.to_string(),
@@ -276,8 +396,9 @@ impl SegmentSlice {
                             value: segment_data,
                         };
                         // send data: this waits until there is capacity in the channel.
-                        if let Err(e) = tx.send(Ok(data)).await {
+                        if let Err(e) = tx.send((read_epoch, Ok(data))).await {
                             info!("Error while sending segment data to event parser {:?} ", e);
+                            prefetch_limit.add_permits(len);
                             break;
                         }
                         offset += len as i64;
@@ -285,8 +406,9 @@ impl SegmentSlice {
                 }
                 Err(e) => {
                     warn!("Error while reading from segment {:?}", e);
+                    prefetch_limit.add_permits(read_buffer_size as usize);
                     if !e.can_retry() {
-                        let _s = tx.send(Err((e, offset))).await;
+                        let _s = tx.send((read_epoch, Err((e, offset)))).await;
                         break;
                     }
                 }
@@ -311,37 +433,41 @@ impl SegmentSlice {
 
     ///
     /// Extract the next event from the data received from the Segment store.
-    /// Note: This returns a copy of the data received.
+    /// Note: The value of the event is split off the received data without copying it.
     /// Return None in case of a Partial data.
     ///
-    fn extract_event(
-        &mut self,
-        parse_header: fn(&mut SegmentDataBuffer) -> Option<SegmentDataBuffer>,
-    ) -> Option<Event> {
-        if let Some(mut event_data) = parse_header(&mut self.meta.segment_data) {
-            let bytes_to_read = event_data.value.capacity();
+    fn extract_event(&mut self, parse_header: fn(&SegmentDataBuffer) -> Option<usize>) -> Option<EventBytes> {
+        if let Some(bytes_to_read) = parse_header(&self.meta.segment_data) {
             if bytes_to_read == 0 {
                 warn!("Found a header with length as zero");
                 return None;
             }
             if self.meta.segment_data.value.remaining() >= bytes_to_read + TYPE_PLUS_LENGTH_SIZE as usize {
+                let event_offset = self.meta.segment_data.offset_in_segment;
                 self.meta.segment_data.advance(TYPE_PLUS_LENGTH_SIZE as usize);
                 // all the data of the event is already present.
-                let t = self.meta.segment_data.split_to(bytes_to_read);
-                event_data.value.put(t.value);
-                info!("extract event data with length {}", event_data.value.len());
+                let event_data = self.meta.segment_data.split_to(bytes_to_read);
+                debug!("extract event data with length {}", bytes_to_read);
                 //Convert to Event and send it.
-                let event = Event {
-                    offset_in_segment: event_data.offset_in_segment,
-                    value: event_data.value.freeze().to_vec(),
+                let segment = self.meta.get_scoped_segment().clone();
+                let event_length = (bytes_to_read + TYPE_PLUS_LENGTH_SIZE as usize) as i32;
+                let event = EventBytes {
+                    offset_in_segment: event_offset,
+                    value: event_data.value.freeze(),
+                    event_pointer: EventPointerVersioned::V1(EventPointerV1::new(
+                        segment,
+                        event_offset,
+                        event_length,
+                    )),
+                    ack_handle: None,
                 };
                 Some(event)
             } else {
                 // complete data for a given event is not present in the buffer.
                 debug!(
                     "partial event read: data read length {}, target read length {}",
-                    event_data.value.len(),
-                    event_data.value.capacity()
+                    self.meta.segment_data.value.remaining(),
+                    bytes_to_read + TYPE_PLUS_LENGTH_SIZE as usize
                 );
                 self.meta.partial_data_present = true;
                 None
@@ -353,23 +479,18 @@ impl SegmentSlice {
     }
 
     ///
-    /// This method reads the header and returns a BytesMut whose size is as big as the event.
+    /// This method reads the header and returns the length of the event.
     /// If complete header is not present return None.
     ///
-    fn read_header(data: &mut SegmentDataBuffer) -> Option<SegmentDataBuffer> {
+    fn read_header(data: &SegmentDataBuffer) -> Option<usize> {
         if data.value.len() >= TYPE_PLUS_LENGTH_SIZE as usize {
-            let event_offset = data.offset_in_segment;
             //workaround since we cannot go back in the position using BytesMut
             let mut bytes_temp = data.value.bytes();
             let type_code = bytes_temp.get_i32();
             let len = bytes_temp.get_i32();
             assert_eq!(type_code, EventCommand::TYPE_CODE, "Expected EventCommand here.");
             debug!("Event size is {}", len);
-            Some(SegmentDataBuffer {
-                segment: data.segment.clone(),
-                offset_in_segment: event_offset,
-                value: BytesMut::with_capacity(len as usize),
-            })
+            Some(len as usize)
         } else {
             None
         }
@@ -378,20 +499,18 @@ impl SegmentSlice {
     pub fn is_empty(&self) -> bool {
         self.meta.segment_data.value.is_empty() || self.meta.partial_data_present
     }
-}
 
-///
-/// Iterator implementation of SegmentSlice.
-///
-impl Iterator for SegmentSlice {
-    type Item = Event;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    ///
+    /// Read the next event of the segment slice without copying its data. The value of the
+    /// returned event is a view into the buffer read from the Segment store, the iterator
+    /// implementation of SegmentSlice returns a copy of it instead.
+    ///
+    pub fn next_bytes(&mut self) -> Option<EventBytes> {
         // extract event from already fetched data.
         let res = self.extract_event(SegmentSlice::read_header);
 
         match res {
-            Some(event) => {
+            Some(mut event) => {
                 self.meta.last_event_offset = event.offset_in_segment;
                 self.meta.read_offset =
                     event.offset_in_segment + event.value.len() as i64 + TYPE_PLUS_LENGTH_SIZE as i64;
@@ -401,6 +520,10 @@ impl Iterator for SegmentSlice {
                         "Error in offset computation"
                     );
                 }
+                if let Some(tracker) = &self.ack_tracker {
+                    let segment = ScopedSegment::from(self.meta.scoped_segment.as_str());
+                    event.ack_handle = Some(tracker.register(segment, event.offset_in_segment));
+                }
                 Some(event)
             }
             None => {
@@ -418,6 +541,17 @@ impl Iterator for SegmentSlice {
     }
 }
 
+///
+/// Iterator implementation of SegmentSlice.
+///
+impl Iterator for SegmentSlice {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_bytes().map(Event::from)
+    }
+}
+
 // Ensure a Drop of Segment slice releases the segment back to the reader group.
 impl Drop for SegmentSlice {
     fn drop(&mut self) {
@@ -432,28 +566,124 @@ impl Drop for SegmentSlice {
 mod tests {
 
     use super::*;
+    use crate::create_stream;
     use bytes::{Buf, BufMut, BytesMut};
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use std::io::Write;
     use std::iter;
+    use std::time::Duration;
     use tokio::sync::mpsc;
     use tokio::sync::mpsc::Sender;
+    use tokio::time::timeout;
 
     ///
-    /// This method reads the header and returns a BytesMut whose size is as big as the event.
+    /// This method reads the header and returns the length of the event.
     ///
-    fn custom_read_header(data: &mut SegmentDataBuffer) -> Option<SegmentDataBuffer> {
+    fn custom_read_header(data: &SegmentDataBuffer) -> Option<usize> {
         if data.value.remaining() >= 4 {
             let mut temp = data.value.bytes();
             let len = temp.get_i32();
-            Some(SegmentDataBuffer {
-                segment: data.segment.clone(),
-                offset_in_segment: 0,
-                value: BytesMut::with_capacity(len as usize),
-            })
+            Some(len as usize)
         } else {
             None
         }
     }
 
+    #[test]
+    fn test_prefetch_limit() {
+        let cf = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let segment = ScopedSegment::from("scope/test/0.#epoch.0");
+        cf.get_runtime().block_on(create_stream(&cf, "scope", "test"));
+        let mut writer = cf.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 100]).expect("write to segment");
+        writer.flush().expect("flush segment");
+
+        // the prefetch limit allows a single outstanding read of 10 bytes.
+        let prefetch_limit = Arc::new(Semaphore::new(10));
+        let (tx, mut rx) = mpsc::channel(1);
+        let (_tx_stop, rx_stop) = oneshot::channel();
+        cf.get_runtime().block_on(async {
+            tokio::spawn(SegmentSlice::get_segment_data(
+                segment,
+                0,
+                0,
+                tx,
+                rx_stop,
+                cf.clone(),
+                10,
+                prefetch_limit.clone(),
+            ));
+            let (_, data) = rx.recv().await.expect("receive data");
+            let data = data.expect("read segment");
+            assert_eq!(data.offset_in_segment, 0);
+            assert_eq!(data.value.len(), 10);
+
+            // no more data is read until the received data is returned to the prefetch limit.
+            assert!(timeout(Duration::from_millis(100), rx.recv()).await.is_err());
+            prefetch_limit.add_permits(data.value.len());
+            let (_, data) = rx.recv().await.expect("receive data");
+            let data = data.expect("read segment");
+            assert_eq!(data.offset_in_segment, 10);
+        });
+    }
+
+    #[test]
+    fn test_read_at_tail() {
+        let cf = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .unwrap(),
+        );
+        let segment = ScopedSegment::from("scope/tail/0.#epoch.0");
+        cf.get_runtime().block_on(create_stream(&cf, "scope", "tail"));
+        let mut writer = cf.create_byte_stream_writer(segment.clone());
+        writer.write_all(&[1; 10]).expect("write to segment");
+        writer.flush().expect("flush segment");
+
+        let prefetch_limit = Arc::new(Semaphore::new(1000));
+        let (tx, mut rx) = mpsc::channel(1);
+        let (_tx_stop, rx_stop) = oneshot::channel();
+        cf.get_runtime().spawn(SegmentSlice::get_segment_data(
+            segment,
+            0,
+            0,
+            tx,
+            rx_stop,
+            cf.clone(),
+            100,
+            prefetch_limit,
+        ));
+        cf.get_runtime().block_on(async {
+            let (_, data) = rx.recv().await.expect("receive data");
+            let data = data.expect("read segment");
+            assert_eq!(data.offset_in_segment, 0);
+            assert_eq!(data.value.len(), 10);
+
+            // the empty reads at the tail are not sent to the reader.
+            assert!(timeout(Duration::from_millis(100), rx.recv()).await.is_err());
+        });
+
+        writer.write_all(&[1; 10]).expect("write to segment");
+        writer.flush().expect("flush segment");
+        cf.get_runtime().block_on(async {
+            let (_, data) = rx.recv().await.expect("receive data");
+            let data = data.expect("read segment");
+            assert_eq!(data.offset_in_segment, 10);
+            assert_eq!(data.value.len(), 10);
+        });
+    }
+
     #[tokio::test]
     async fn test_read_partial_events_buffer_10() {
         let (tx, mut rx) = mpsc::channel(1);
@@ -475,7 +705,21 @@ mod tests {
                 assert_eq!(expected_offset, d.offset_in_segment as usize);
                 assert_eq!(expected_event_len, d.value.len());
                 assert!(is_all_same(d.value.as_slice()));
+                assert_eq!(
+                    EventPointerVersioned::V1(EventPointerV1::new(
+                        ScopedSegment::from("test/test/123"),
+                        expected_offset as i64,
+                        (8 + expected_event_len) as i32
+                    )),
+                    d.event_pointer
+                );
                 expected_offset += 8 + expected_event_len;
+                assert_eq!(
+                    Some(&(expected_offset as i64)),
+                    d.get_position()
+                        .get_owned_segments_with_offsets()
+                        .get(&ScopedSegment::from("test/test/123"))
+                );
                 expected_event_len += 1;
             }
         }
@@ -510,6 +754,35 @@ mod tests {
         assert_eq!(200, expected_event_len);
     }
 
+    #[tokio::test]
+    async fn test_read_events_as_bytes() {
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(generate_variable_size_events(tx, 100, 200));
+        let mut segment_slice = create_segment_slice();
+        let mut expected_offset: usize = 0;
+        let mut expected_event_len = 1;
+
+        loop {
+            if segment_slice.is_empty() {
+                if let Some(response) = rx.recv().await {
+                    segment_slice.meta.segment_data.value.put(response.value);
+                } else {
+                    break; // All events are sent.
+                }
+            }
+
+            while let Some(d) = segment_slice.next_bytes() {
+                assert_eq!(expected_offset, d.offset_in_segment as usize);
+                assert_eq!(expected_event_len, d.value.len());
+                assert!(is_all_same(&d.value));
+                expected_offset += 8 + expected_event_len;
+                assert_eq!(segment_slice.meta.read_offset, expected_offset as i64);
+                expected_event_len += 1;
+            }
+        }
+        assert_eq!(200, expected_event_len);
+    }
+
     // create a segment slice for testing.
     fn create_segment_slice() -> SegmentSlice {
         let segment = ScopedSegment::from("test/test/123");
@@ -522,8 +795,10 @@ mod tests {
                 end_offset: i64::MAX,
                 segment_data: SegmentDataBuffer::empty(),
                 partial_data_present: false,
+                parsed_segment: None,
             },
             slice_return_tx: None,
+            ack_tracker: None,
         };
         segment_slice
     }
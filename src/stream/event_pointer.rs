@@ -8,7 +8,7 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 //
 use crate::error::*;
-use pravega_client_shared::Segment;
+use pravega_client_shared::ScopedSegment;
 use serde::{Deserialize, Serialize};
 use serde_cbor::from_slice;
 use serde_cbor::to_vec;
@@ -16,29 +16,60 @@ use snafu::ResultExt;
 
 /// EventPointerVersioned enum contains all versions of EventPointer
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
-pub(crate) enum EventPointerVersioned {
+pub enum EventPointerVersioned {
     V1(EventPointerV1),
 }
 
 impl EventPointerVersioned {
-    fn to_bytes(&self) -> Result<Vec<u8>, SerdeError> {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerdeError> {
         let encoded = to_vec(&self).context(Cbor {
             msg: "serialize EventPointerVersioned".to_owned(),
         })?;
         Ok(encoded)
     }
 
-    fn from_bytes(input: &[u8]) -> Result<EventPointerVersioned, SerdeError> {
+    pub fn from_bytes(input: &[u8]) -> Result<EventPointerVersioned, SerdeError> {
         let decoded: EventPointerVersioned = from_slice(&input[..]).context(Cbor {
             msg: "deserialize EventPointerVersioned".to_owned(),
         })?;
         Ok(decoded)
     }
+
+    ///
+    /// Return the segment the event was read from.
+    ///
+    pub fn get_segment(&self) -> &ScopedSegment {
+        match self {
+            EventPointerVersioned::V1(v1) => &v1.segment,
+        }
+    }
+
+    ///
+    /// Return the offset of the event in the segment, this includes the event header.
+    ///
+    pub fn get_event_start_offset(&self) -> i64 {
+        match self {
+            EventPointerVersioned::V1(v1) => v1.event_start_offset,
+        }
+    }
+
+    ///
+    /// Return the length of the event in the segment, this includes the event header.
+    ///
+    pub fn get_event_length(&self) -> i32 {
+        match self {
+            EventPointerVersioned::V1(v1) => v1.event_length,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
-pub(crate) struct EventPointerV1 {
-    segment: Segment,
+///
+/// EventPointerV1 points to a single event in a Pravega segment. It can be persisted and used
+/// later to fetch the event using [ClientFactory#fetch_event](crate::client_factory::ClientFactory#fetch_event).
+///
+#[derive(new, Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct EventPointerV1 {
+    segment: ScopedSegment,
     event_start_offset: i64,
     event_length: i32,
 }
@@ -50,10 +81,7 @@ mod tests {
     #[test]
     fn test_event_pointer_serde() {
         let v1 = EventPointerV1 {
-            segment: Segment {
-                number: 0,
-                tx_id: None,
-            },
+            segment: ScopedSegment::from("test/test/0.#epoch.0"),
             event_start_offset: 0,
             event_length: 0,
         };
@@ -8,7 +8,7 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 //
 use crate::error::*;
-use pravega_client_shared::{Segment, SegmentWithRange};
+use pravega_client_shared::{ScopedSegment, SegmentWithRange};
 use serde::{Deserialize, Serialize};
 use serde_cbor::from_slice;
 use serde_cbor::to_vec;
@@ -17,39 +17,52 @@ use std::collections::HashMap;
 
 /// PositionedVersioned enum contains all versions of Position struct
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
-pub(crate) enum PositionVersioned {
+pub enum PositionVersioned {
     V1(PositionV1),
 }
 
 impl PositionVersioned {
-    fn to_bytes(&self) -> Result<Vec<u8>, SerdeError> {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerdeError> {
         let encoded = to_vec(&self).context(Cbor {
             msg: "serialize PositionVersioned".to_owned(),
         })?;
         Ok(encoded)
     }
 
-    fn from_bytes(input: &[u8]) -> Result<PositionVersioned, SerdeError> {
+    pub fn from_bytes(input: &[u8]) -> Result<PositionVersioned, SerdeError> {
         let decoded: PositionVersioned = from_slice(&input[..]).context(Cbor {
             msg: "serialize PositionVersioned".to_owned(),
         })?;
         Ok(decoded)
     }
+
+    ///
+    /// Return the segments and the offsets at which reading should resume.
+    ///
+    pub fn get_owned_segments_with_offsets(&self) -> HashMap<ScopedSegment, i64> {
+        match self {
+            PositionVersioned::V1(v1) => v1.get_owned_segments_with_offsets(),
+        }
+    }
 }
 
+///
+/// PositionV1 represents the offsets up to which a reader has read its segments. Reading resumes
+/// from these offsets.
+///
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
-pub(crate) struct PositionV1 {
-    owned_segments: HashMap<Segment, i64>,
-    segment_ranges: HashMap<Segment, SegmentWithRange>,
+pub struct PositionV1 {
+    owned_segments: HashMap<ScopedSegment, i64>,
+    segment_ranges: HashMap<ScopedSegment, SegmentWithRange>,
 }
 
 impl PositionV1 {
-    pub(crate) fn new(segments: HashMap<SegmentWithRange, i64>) -> Self {
+    pub fn new(segments: HashMap<SegmentWithRange, i64>) -> Self {
         let mut owned_segments = HashMap::with_capacity(segments.len());
         let mut segment_ranges = HashMap::with_capacity(segments.len());
         for (k, v) in segments {
-            owned_segments.insert(k.get_segment(), v);
-            segment_ranges.insert(k.get_segment(), k);
+            owned_segments.insert(k.scoped_segment.clone(), v);
+            segment_ranges.insert(k.scoped_segment.clone(), k);
         }
         PositionV1 {
             owned_segments,
@@ -57,7 +70,18 @@ impl PositionV1 {
         }
     }
 
-    pub(crate) fn get_owned_segments_with_offsets(&self) -> HashMap<Segment, i64> {
+    ///
+    /// Create a position from the segments and offsets only, the key ranges of the segments are
+    /// not known.
+    ///
+    pub fn from_offsets(segments: HashMap<ScopedSegment, i64>) -> Self {
+        PositionV1 {
+            owned_segments: segments,
+            segment_ranges: HashMap::new(),
+        }
+    }
+
+    pub fn get_owned_segments_with_offsets(&self) -> HashMap<ScopedSegment, i64> {
         self.owned_segments.to_owned()
     }
 }
@@ -66,7 +90,7 @@ impl PositionV1 {
 mod tests {
     use super::*;
     use ordered_float::OrderedFloat;
-    use pravega_client_shared::{Scope, ScopedSegment, Stream};
+    use pravega_client_shared::{Scope, Segment, Stream};
 
     #[test]
     fn test_position_serde() {
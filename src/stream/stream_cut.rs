@@ -8,18 +8,47 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 //
 use crate::error::*;
-use pravega_client_shared::{ScopedSegment, ScopedStream};
+use pravega_client_retry::retry_result::RetryError;
+use pravega_client_shared::{ScopedSegment, ScopedStream, Segment, StreamCut};
+use pravega_controller_client::{ControllerClient, ControllerError};
 use serde::{Deserialize, Serialize};
 use serde_cbor::from_slice;
 use serde_cbor::to_vec;
 use snafu::ResultExt;
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///
+/// Build the stream cut of a stream at the given wall clock time. The stream cut points to the
+/// first events written at or after the given time, or to the head of the stream if the stream
+/// has been truncated after that time.
+///
+pub async fn stream_cut_at_time(
+    controller_client: &dyn ControllerClient,
+    stream: &ScopedStream,
+    time: SystemTime,
+) -> Result<StreamCut, RetryError<ControllerError>> {
+    let timestamp = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64);
+    let segments = controller_client.get_segments_at_time(stream, timestamp).await?;
+    Ok(StreamCut::new(
+        stream.clone(),
+        segments
+            .into_iter()
+            .map(|(segment, offset)| (segment.number, offset))
+            .collect(),
+    ))
+}
 
 /// StreamCutVersioned enum contains all versions of StreamCut struct
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub(crate) enum StreamCutVersioned {
     V1(StreamCutV1),
     UNBOUNDED,
+    /// The tail of the stream when the reader group is created, this is replaced by the stream cut
+    /// of the tail before the config of the reader group is stored.
+    Tail,
 }
 
 impl StreamCutVersioned {
@@ -48,6 +77,31 @@ pub(crate) struct StreamCutV1 {
     positions: HashMap<ScopedSegment, i64>,
 }
 
+/// A stream cut without any segment points to the head of the stream.
+impl From<StreamCut> for StreamCutVersioned {
+    fn from(stream_cut: StreamCut) -> Self {
+        if stream_cut.segment_offset_map.is_empty() {
+            return StreamCutVersioned::UNBOUNDED;
+        }
+        let stream = stream_cut.scoped_stream;
+        let positions = stream_cut
+            .segment_offset_map
+            .into_iter()
+            .map(|(segment, offset)| {
+                (
+                    ScopedSegment {
+                        scope: stream.scope.clone(),
+                        stream: stream.stream.clone(),
+                        segment: Segment::from(segment),
+                    },
+                    offset,
+                )
+            })
+            .collect();
+        StreamCutVersioned::V1(StreamCutV1::new(stream, positions))
+    }
+}
+
 impl StreamCutV1 {
     pub(crate) fn new(stream: ScopedStream, positions: HashMap<ScopedSegment, i64>) -> Self {
         StreamCutV1 { stream, positions }
@@ -67,7 +121,11 @@ impl StreamCutV1 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use pravega_client_shared::{Scope, Segment, Stream};
+    use crate::client_factory::ClientFactory;
+    use crate::create_stream;
+    use pravega_client_config::connection_type::{ConnectionType, MockType};
+    use pravega_client_config::{ClientConfigBuilder, MOCK_CONTROLLER_URI};
+    use pravega_client_shared::{Scope, Stream};
 
     #[test]
     fn test_position_serde() {
@@ -89,4 +147,44 @@ mod tests {
         let decoded = StreamCutVersioned::from_bytes(&encoded).expect("decode from byte array");
         assert_eq!(StreamCutVersioned::V1(v1), decoded);
     }
+
+    #[test]
+    fn test_stream_cut_at_time() {
+        let client_factory = ClientFactory::new(
+            ClientConfigBuilder::default()
+                .connection_type(ConnectionType::Mock(MockType::Happy))
+                .mock(true)
+                .controller_uri(MOCK_CONTROLLER_URI)
+                .build()
+                .expect("build client config"),
+        );
+        let rt = client_factory.get_runtime();
+        rt.block_on(create_stream(&client_factory, "scope", "stream"));
+        let stream = ScopedStream::from("scope/stream");
+        let stream_cut = rt
+            .block_on(stream_cut_at_time(
+                client_factory.get_controller_client(),
+                &stream,
+                SystemTime::now(),
+            ))
+            .expect("get stream cut at time");
+        assert_eq!(stream_cut.scoped_stream, stream);
+        assert_eq!(stream_cut.segment_offset_map.get(&0), Some(&0));
+
+        match StreamCutVersioned::from(stream_cut) {
+            StreamCutVersioned::V1(v1) => {
+                assert_eq!(v1.get_stream(), stream);
+                assert_eq!(
+                    v1.get_positions()
+                        .get(&ScopedSegment::from("scope/stream/0.#epoch.0")),
+                    Some(&0)
+                );
+            }
+            _ => panic!("stream cut should be bounded"),
+        }
+        assert_eq!(
+            StreamCutVersioned::from(StreamCut::new(stream, HashMap::new())),
+            StreamCutVersioned::UNBOUNDED
+        );
+    }
 }
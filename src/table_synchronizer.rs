@@ -82,6 +82,23 @@ impl TableSynchronizer {
         }
     }
 
+    /// Opens the synchronizer of an existing table map without creating its table segment.
+    pub async fn open(
+        scope: Scope,
+        name: String,
+        factory: ClientFactory,
+    ) -> Result<TableSynchronizer, TableError> {
+        let table_map = TableMap::open(scope, name.clone(), factory).await?;
+        Ok(TableSynchronizer {
+            name,
+            table_map,
+            in_memory_map: HashMap::new(),
+            in_memory_map_version: HashMap::new(),
+            table_segment_offset: -1,
+            fetch_position: 0,
+        })
+    }
+
     /// Gets the outer map currently held in memory.
     /// The return type does not contain the version information.
     pub fn get_outer_map(&self) -> HashMap<String, HashMap<String, Value>> {
@@ -235,12 +252,7 @@ impl TableSynchronizer {
                     self.fetch_position = last_position;
                     counter += 1;
                 }
-                _ => {
-                    return Err(TableError::OperationError {
-                        operation: "fetch_updates".to_string(),
-                        error_msg: "fetch entries error".to_string(),
-                    });
-                }
+                Err(e) => return Err(e),
             }
         }
         debug!("finished fetching updates");
@@ -256,6 +268,12 @@ impl TableSynchronizer {
         conditionally_write(updates_generator, self, MAX_RETRIES).await
     }
 
+    /// Deletes the table segment that stores the map on the server side.
+    /// The in memory map is not affected.
+    pub async fn delete(&self) -> Result<(), TableError> {
+        self.table_map.delete().await
+    }
+
     /// Removes a list of keys and applies it atomically to local map.
     /// This will update the local_map to latest version.
     pub async fn remove(
@@ -20,9 +20,9 @@ use pravega_client_retry::retry_result::RetryResult;
 use pravega_client_shared::{PravegaNodeUri, Stream as PravegaStream};
 use pravega_client_shared::{Scope, ScopedSegment, ScopedStream, Segment};
 use pravega_wire_protocol::commands::{
-    CreateTableSegmentCommand, ReadTableCommand, ReadTableEntriesCommand, ReadTableEntriesDeltaCommand,
-    ReadTableKeysCommand, RemoveTableKeysCommand, TableEntries, TableKey, TableValue,
-    UpdateTableEntriesCommand,
+    CreateTableSegmentCommand, DeleteTableSegmentCommand, ReadTableCommand, ReadTableEntriesCommand,
+    ReadTableEntriesDeltaCommand, ReadTableKeysCommand, RemoveTableKeysCommand, TableEntries, TableKey,
+    TableValue, UpdateTableEntriesCommand,
 };
 use pravega_wire_protocol::wire_commands::{Replies, Requests};
 use serde::Serialize;
@@ -61,15 +61,13 @@ pub enum TableError {
     IncorrectKeyVersion { operation: String, error_msg: String },
     #[snafu(display("Error observed while performing {} due to {}", operation, error_msg,))]
     OperationError { operation: String, error_msg: String },
+    #[snafu(display("Table {} does not exist while performing {}", name, operation))]
+    TableDoesNotExist { operation: String, name: String },
 }
 impl TableMap {
     /// create a table map
     pub async fn new(scope: Scope, name: String, factory: ClientFactory) -> Result<TableMap, TableError> {
-        let segment = ScopedSegment {
-            scope,
-            stream: PravegaStream::from(format!("{}{}", name, KVTABLE_SUFFIX)),
-            segment: Segment::from(0),
-        };
+        let segment = TableMap::table_segment(scope, &name);
         info!("creating table map on {:?}", segment);
 
         let delegation_token_provider = factory
@@ -132,6 +130,56 @@ impl TableMap {
         })
     }
 
+    ///
+    /// Deletes the table segment backing this table map, including all its entries.
+    /// Deleting a table segment that does not exist is not treated as an error.
+    ///
+    pub async fn delete(&self) -> Result<(), TableError> {
+        delete_table_segment(
+            &self.factory,
+            self.endpoint.clone(),
+            &self.name,
+            &self.delegation_token_provider,
+        )
+        .await
+    }
+
+    ///
+    /// Open an existing table map without creating its table segment. Reading a table map whose
+    /// table segment does not exist returns [`TableError::TableDoesNotExist`].
+    ///
+    pub async fn open(scope: Scope, name: String, factory: ClientFactory) -> Result<TableMap, TableError> {
+        let segment = TableMap::table_segment(scope, &name);
+        info!("opening table map on {:?}", segment);
+
+        let delegation_token_provider = factory
+            .create_delegation_token_provider(ScopedStream::from(&segment))
+            .await;
+        let endpoint = factory
+            .get_controller_client()
+            .get_endpoint_for_segment(&segment)
+            .await
+            .map_err(|e| TableError::OperationError {
+                operation: "Open table segment".to_string(),
+                error_msg: format!("failed to get endpoint for segment: {:?}", e),
+            })?;
+        Ok(TableMap {
+            name: segment.to_string(),
+            endpoint,
+            factory,
+            delegation_token_provider,
+        })
+    }
+
+    // the segment that stores the table map with the given name
+    fn table_segment(scope: Scope, name: &str) -> ScopedSegment {
+        ScopedSegment {
+            scope,
+            stream: PravegaStream::from(format!("{}{}", name, KVTABLE_SUFFIX)),
+            segment: Segment::from(0),
+        }
+    }
+
     ///
     /// Returns the latest value corresponding to the key.
     ///
@@ -890,6 +938,10 @@ impl TableMap {
 
                     Ok((entries, c.last_position))
                 }
+                Replies::NoSuchSegment(..) => Err(TableError::TableDoesNotExist {
+                    operation: op.into(),
+                    name: self.name.clone(),
+                }),
                 // unexpected response from Segment store causes a panic.
                 _ => Err(TableError::OperationError {
                     operation: op.into(),
@@ -900,6 +952,57 @@ impl TableMap {
     }
 }
 
+// sends a DeleteTableSegment request, treating a missing segment as already deleted
+async fn delete_table_segment(
+    factory: &ClientFactory,
+    endpoint: PravegaNodeUri,
+    name: &str,
+    delegation_token_provider: &DelegationTokenProvider,
+) -> Result<(), TableError> {
+    let op = "Delete table segment";
+    retry_async(factory.get_config().retry_policy, || async {
+        let req = Requests::DeleteTableSegment(DeleteTableSegmentCommand {
+            request_id: get_request_id(),
+            segment: name.to_string(),
+            must_be_empty: false,
+            delegation_token: delegation_token_provider
+                .retrieve_token(factory.get_controller_client())
+                .await,
+        });
+        let result = factory
+            .create_raw_client_for_endpoint(endpoint.clone())
+            .send_request(&req)
+            .await;
+        debug!("Reply for DeleteTableSegment request {:?}", result);
+        match result {
+            Ok(reply) => RetryResult::Success(reply),
+            Err(e) => {
+                if e.is_token_expired() {
+                    delegation_token_provider.signal_token_expiry();
+                    info!("auth token needs to refresh");
+                }
+                RetryResult::Retry(e)
+            }
+        }
+    })
+    .await
+    .map_err(|e| TableError::ConnectionError {
+        can_retry: true,
+        operation: op.into(),
+        source: e.error,
+    })
+    .and_then(|r| match r {
+        Replies::SegmentDeleted(..) | Replies::NoSuchSegment(..) => {
+            info!("Table segment {:?} deleted", name);
+            Ok(())
+        }
+        _ => Err(TableError::OperationError {
+            operation: op.into(),
+            error_msg: r.to_string(),
+        }),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -156,6 +156,20 @@ impl TransactionalEventStreamWriter {
         )
         .await)
     }
+
+    //
+    // Abort the transaction with the given id, this is used to abort the transactions which were
+    // left open by a previous writer without fetching them.
+    //
+    pub(crate) async fn abort_txn(&self, txn_id: TxId) -> Result<(), TransactionalEventStreamWriterError> {
+        self.pinger_handle.clone().remove(txn_id).await?;
+        self.factory
+            .get_controller_client()
+            .abort_transaction(&self.stream, txn_id)
+            .await
+            .map_err(|e| e.error)
+            .context(TxnStreamControllerError {})
+    }
 }
 
 #[cfg(test)]
@@ -176,6 +190,14 @@ pub(crate) mod test {
             .block_on(txn_stream_writer.get_txn(transaction.get_txn_id()))
             .expect("get transaction");
         assert_eq!(transaction.get_txn_id(), fetched_transaction.get_txn_id());
+
+        // the transaction can be aborted by its id.
+        rt.block_on(txn_stream_writer.abort_txn(transaction.get_txn_id()))
+            .expect("abort transaction");
+        assert_eq!(
+            rt.block_on(transaction.check_status()).expect("check status"),
+            TransactionStatus::Aborted
+        );
     }
 
     // helper function
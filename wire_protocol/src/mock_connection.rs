@@ -10,11 +10,12 @@
 
 extern crate byteorder;
 use crate::commands::{
-    AppendSetupCommand, ConditionalCheckFailedCommand, DataAppendedCommand, SegmentAlreadyExistsCommand,
-    SegmentCreatedCommand, SegmentIsSealedCommand, SegmentIsTruncatedCommand, SegmentReadCommand,
-    SegmentSealedCommand, SegmentTruncatedCommand, StreamSegmentInfoCommand, TableEntries,
-    TableEntriesDeltaReadCommand, TableEntriesUpdatedCommand, TableKey, TableKeyBadVersionCommand,
-    TableKeyDoesNotExistCommand, TableKeysRemovedCommand, TableReadCommand, TableValue, WrongHostCommand,
+    AppendSetupCommand, ConditionalCheckFailedCommand, DataAppendedCommand, NoSuchSegmentCommand,
+    SegmentAlreadyExistsCommand, SegmentCreatedCommand, SegmentDeletedCommand, SegmentIsSealedCommand,
+    SegmentIsTruncatedCommand, SegmentReadCommand, SegmentSealedCommand, SegmentTruncatedCommand,
+    StreamSegmentInfoCommand, TableEntries, TableEntriesDeltaReadCommand, TableEntriesUpdatedCommand,
+    TableKey, TableKeyBadVersionCommand, TableKeyDoesNotExistCommand, TableKeysRemovedCommand,
+    TableReadCommand, TableValue, WrongHostCommand,
 };
 use crate::connection::{Connection, ConnectionReadHalf, ConnectionWriteHalf};
 use crate::error::*;
@@ -472,8 +473,37 @@ async fn send_happy(
             });
             sender.send(reply).expect("send reply");
         }
+        Requests::DeleteTableSegment(cmd) => {
+            let reply = if table_segment.remove(&cmd.segment).is_some() {
+                table_segment_index.remove(&cmd.segment);
+                Replies::SegmentDeleted(SegmentDeletedCommand {
+                    request_id: cmd.request_id,
+                    segment: cmd.segment,
+                })
+            } else {
+                Replies::NoSuchSegment(NoSuchSegmentCommand {
+                    request_id: cmd.request_id,
+                    segment: cmd.segment,
+                    server_stack_trace: "".to_string(),
+                    offset: 0,
+                })
+            };
+            sender.send(reply).expect("send reply");
+        }
         Requests::ReadTableEntriesDelta(cmd) => {
-            let segment = table_segment.get_mut(&cmd.segment).expect("get table segment");
+            let segment = match table_segment.get_mut(&cmd.segment) {
+                Some(segment) => segment,
+                None => {
+                    let reply = Replies::NoSuchSegment(NoSuchSegmentCommand {
+                        request_id: cmd.request_id,
+                        segment: cmd.segment,
+                        server_stack_trace: "".to_string(),
+                        offset: 0,
+                    });
+                    sender.send(reply).expect("send reply");
+                    return Ok(());
+                }
+            };
             let mut delta = vec![];
 
             let to_position = cmp::min(